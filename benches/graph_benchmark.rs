@@ -1,3 +1,5 @@
+#![allow(clippy::needless_return)]
+
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
 fn criterion_benchmark(c: &mut Criterion) {
@@ -13,7 +15,7 @@ fn criterion_benchmark(c: &mut Criterion) {
     graph.add_edge(1, 2, 7);
     graph.add_edge(2, 3, 8);
 
-    fn heuristic(_node: u32, _target: u32) -> u32 {
+    fn heuristic(_node: u32, _target: u32) -> i32 {
         return 1;
     }
 
@@ -21,7 +23,29 @@ fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("DFS", |b| b.iter(|| graph.depth_first_search(black_box(0))));
     c.bench_function("Dijkstra", |b| b.iter(|| graph.shortest_path(black_box(0), black_box(3))));
     c.bench_function("A*", |b| b.iter(|| graph.astar(black_box(0), black_box(3), black_box(heuristic))));
-    c.bench_function("Bellman-Ford", |b| b.iter(|| graph.bellman_ford(black_box(0))));
+    c.bench_function("Bellman-Ford", |b| b.iter(|| graph.bellman_ford(black_box(0)).distances));
+    c.bench_function("SPFA", |b| b.iter(|| graph.spfa(black_box(0)).distances));
+
+    // 40x40 grid with a few negative edges, Bellman-Ford needs many passes to converge on it
+    let cells: Vec<Vec<Option<i32>>> = (0..40).map(|y| (0..40).map(|x| Some(1 + (x * 3 + y) % 5)).collect()).collect();
+    let mut weighted_grid: mgraph::Graph = mgraph::Graph::from_grid(&cells, &mgraph::GridOptions::default()).unwrap();
+    for node in (0..1600).step_by(97) {
+        weighted_grid.add_edge_directed(node, node + 1, -1);
+    }
+
+    c.bench_function("Bellman-Ford (grid)", |b| b.iter(|| weighted_grid.bellman_ford(black_box(0)).distances));
+    c.bench_function("SPFA (grid)", |b| b.iter(|| weighted_grid.spfa(black_box(0)).distances));
+
+    // 300x300 grid with scattered walls, queries stay far from its borders
+    let cells: Vec<Vec<bool>> = (0..300).map(|y| (0..300).map(|x| (x * 7 + y * 13) % 11 != 0).collect()).collect();
+    let grid: mgraph::Graph = mgraph::Graph::from_passability_grid(&cells, 1, &mgraph::GridOptions::default()).unwrap();
+    let reverse = grid.reversed();
+    let (source, target) = (150 * 300 + 100, 150 * 300 + 200);
+
+    c.bench_function("Dijkstra (grid)", |b| b.iter(|| grid.shortest_path(black_box(source), black_box(target))));
+    c.bench_function("Bidirectional Dijkstra (grid)", |b| {
+        b.iter(|| grid.bidirectional_shortest_path_with_reverse(&reverse, black_box(source), black_box(target)))
+    });
 
 
 }
@@ -0,0 +1,179 @@
+use crate::{Graph, GraphError, Path, Weight};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Shortest paths between every pair of nodes, returned by `Graph::floyd_warshall()` and `Graph::johnson()`.
+#[derive(Debug, Clone)]
+pub struct AllPairsShortestPaths<N = u32, E = i32> {
+    /// Map of a `(source, target)` pair and the cost of the shortest path between them. Pairs without a path are not present.
+    pub distances: HashMap<(N, N), E>,
+    /// Map of a `(source, target)` pair and the node following `source` on the shortest path to `target`.
+    pub next: HashMap<(N, N), N>,
+}
+
+impl<N: Copy + Eq + Hash, E: Weight> AllPairsShortestPaths<N, E> {
+    /// Returns the cost of the shortest path from `source` to `target`, or `None` if there is no path.
+    pub fn distance(&self, source: N, target: N) -> Option<E> {
+        return self.distances.get(&(source, target)).copied();
+    }
+
+    /// Returns the node following `source` on the shortest path to `target`, or `None` if there is no path or `source` is `target`.
+    pub fn next_hop(&self, source: N, target: N) -> Option<N> {
+        return self.next.get(&(source, target)).copied();
+    }
+
+    /// Returns the shortest path from `source` to `target`, or `None` if there is no path.
+    pub fn path(&self, source: N, target: N) -> Option<Path<N, E>> {
+        let cost = self.distance(source, target)?;
+        let mut nodes: Vec<N> = vec![source];
+        let mut weights: Vec<E> = Vec::new();
+        let mut current_node = source;
+
+        while current_node != target {
+            let next_node = self.next_hop(current_node, target)?;
+
+            // the rest of a shortest path is a shortest path itself, so the edge weight is the difference of the distances
+            weights.push(self.distance(current_node, target)? - self.distance(next_node, target)?);
+            nodes.push(next_node);
+            current_node = next_node;
+        }
+
+        return Some(Path { nodes, weights, cost });
+    }
+}
+
+impl<N: Copy + Eq + Hash, E: Weight> Graph<N, E> {
+    /// Finds the shortest paths between every pair of nodes using Floyd-Warshall algorithm. Works best for dense graphs.
+    /// Negative weights are allowed, returns `GraphError::NegativeCycle` if the graph contains a negative cycle.
+    pub fn floyd_warshall(&self) -> Result<AllPairsShortestPaths<N, E>, GraphError<N>> {
+        let nodes: Vec<N> = self.nodes.keys().copied().collect();
+        let indices: HashMap<N, usize> = nodes.iter().enumerate().map(|(index, &node)| (node, index)).collect();
+        let count = nodes.len();
+
+        let mut distances: Vec<Option<E>> = vec![None; count * count];
+        let mut next: Vec<Option<usize>> = vec![None; count * count];
+
+        for (index, node) in nodes.iter().enumerate() {
+            distances[index * count + index] = Some(E::zero());
+
+            for &(target, weight) in self.adjacents(*node) {
+                let Some(&target_index) = indices.get(&target) else {
+                    continue;
+                };
+
+                let cell = index * count + target_index;
+                if distances[cell].is_none_or(|distance| weight < distance) {
+                    distances[cell] = Some(weight);
+                    next[cell] = Some(target_index);
+                }
+            }
+        }
+
+        for middle in 0..count {
+            for source in 0..count {
+                let Some(to_middle) = distances[source * count + middle] else {
+                    continue;
+                };
+
+                for target in 0..count {
+                    let Some(through_middle) = distances[middle * count + target].and_then(|from_middle| to_middle.checked_add(from_middle)) else {
+                        continue;
+                    };
+
+                    let cell = source * count + target;
+                    if distances[cell].is_none_or(|distance| through_middle < distance) {
+                        distances[cell] = Some(through_middle);
+                        next[cell] = next[source * count + middle];
+                    }
+                }
+            }
+
+            if (0..count).any(|index| distances[index * count + index].is_some_and(|distance| distance < E::zero())) {
+                return Err(GraphError::NegativeCycle(self.find_negative_cycle().unwrap_or_default()));
+            }
+        }
+
+        let mut result = AllPairsShortestPaths { distances: HashMap::new(), next: HashMap::new() };
+
+        for (source_index, &source) in nodes.iter().enumerate() {
+            for (target_index, &target) in nodes.iter().enumerate() {
+                let cell = source_index * count + target_index;
+
+                if let Some(distance) = distances[cell] {
+                    result.distances.insert((source, target), distance);
+                }
+                if let Some(next_index) = next[cell].filter(|_| source != target) {
+                    result.next.insert((source, target), nodes[next_index]);
+                }
+            }
+        }
+
+        return Ok(result);
+    }
+
+    /// Finds the shortest paths between every pair of nodes using Johnson algorithm. Works best for sparse graphs.
+    /// Negative weights are allowed: edges are reweighted using potentials found by Bellman-Ford algorithm, then Dijkstra algorithm is run from every node.
+    /// Returns `GraphError::NegativeCycle` if the graph contains a negative cycle.
+    pub fn johnson(&self) -> Result<AllPairsShortestPaths<N, E>, GraphError<N>> {
+        let mut result = AllPairsShortestPaths { distances: HashMap::new(), next: HashMap::new() };
+
+        let Some(&first_node) = self.nodes.keys().next() else {
+            return Ok(result);
+        };
+
+        let potentials = self.bellman_ford_from(first_node, self.nodes.keys().map(|&node| (node, E::zero())).collect());
+
+        if let Some(cycle) = potentials.negative_cycle {
+            return Err(GraphError::NegativeCycle(cycle));
+        }
+
+        let potentials = potentials.distances;
+
+        let mut reweighted: Graph<N, E> = Graph::default();
+
+        for (&source, adjacents) in &self.nodes {
+            reweighted.nodes.insert(source, adjacents.iter().filter(|(target, _)| self.nodes.contains_key(target)).map(|&(target, weight)| {
+                // potentials[source] + weight >= potentials[target], so the new weight is never negative
+                (target, potentials[&source] + weight - potentials[&target])
+            }).collect());
+        }
+
+        for &source in self.nodes.keys() {
+            let tree = reweighted.shortest_path_tree(source);
+
+            for (&target, &distance) in &tree.distances {
+                result.distances.insert((source, target), distance + potentials[&target] - potentials[&source]);
+            }
+
+            for &target in tree.parents.keys() {
+                if result.next.contains_key(&(source, target)) {
+                    continue;
+                }
+
+                // walk up the tree until a node with a known next hop, then fill in the nodes passed on the way
+                let mut chain: Vec<N> = vec![target];
+                let mut current_node = target;
+
+                let next_hop = loop {
+                    let parent = tree.parents[&current_node].0;
+
+                    if parent == source {
+                        break current_node;
+                    }
+                    if let Some(&next_hop) = result.next.get(&(source, parent)) {
+                        break next_hop;
+                    }
+
+                    chain.push(parent);
+                    current_node = parent;
+                };
+
+                for node in chain {
+                    result.next.insert((source, node), next_hop);
+                }
+            }
+        }
+
+        return Ok(result);
+    }
+}
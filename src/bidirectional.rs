@@ -0,0 +1,137 @@
+use crate::{DijkstraResult, DijkstraState, Graph, Weight};
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+/// State of one direction of a bidirectional search.
+struct SearchFrontier<N, E> {
+    queue: BinaryHeap<DijkstraState<N, E>>,
+    distances: HashMap<N, E>,
+    parents: HashMap<N, (N, E)>,
+}
+
+impl<N: Copy + Eq + Hash, E: Weight> SearchFrontier<N, E> {
+    fn new(start: N) -> Self {
+        return Self {
+            queue: BinaryHeap::from([DijkstraState { node: start, cost: E::zero() }]),
+            distances: HashMap::from([(start, E::zero())]),
+            parents: HashMap::new(),
+        };
+    }
+
+    /// Returns the cost of the next node to be settled, skipping stale queue entries.
+    fn peek(&mut self) -> Option<E> {
+        while let Some(&DijkstraState { node, cost }) = self.queue.peek() {
+            if cost <= self.distances[&node] {
+                return Some(cost);
+            }
+
+            self.queue.pop();
+        }
+
+        return None;
+    }
+
+    /// Settles the next node, relaxing its edges in `graph`.
+    /// Updates `best` with the cost and the meeting node of the shortest path found so far, using distances of the `other` direction.
+    fn settle(&mut self, graph: &Graph<N, E>, other: &SearchFrontier<N, E>, best: &mut Option<(E, N)>) {
+        let Some(DijkstraState { node, cost }) = self.queue.pop() else {
+            return;
+        };
+
+        for &(adjacent, weight) in graph.adjacents(node) {
+            let Some(next_cost) = cost.checked_add(weight) else {
+                continue;
+            };
+
+            if self.distances.get(&adjacent).is_none_or(|&distance| next_cost < distance) {
+                self.queue.push(DijkstraState { node: adjacent, cost: next_cost });
+                self.distances.insert(adjacent, next_cost);
+                self.parents.insert(adjacent, (node, weight));
+
+                if let Some(total_cost) = other.distances.get(&adjacent).and_then(|&other_distance| next_cost.checked_add(other_distance)) {
+                    if best.is_none_or(|(best_cost, _)| total_cost < best_cost) {
+                        *best = Some((total_cost, adjacent));
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<N: Copy + Eq + Hash, E: Weight> Graph<N, E> {
+    /// Finds the shortest path from `source` to `target` by running Dijkstra algorithm from both ends at the same time.
+    /// Returns the same cost and path as `shortest_path()`, but usually explores far fewer nodes on large graphs.
+    /// Builds the reversed graph on every call, use `bidirectional_shortest_path_with_reverse()` to answer many queries.
+    pub fn bidirectional_shortest_path(&self, source: N, target: N) -> DijkstraResult<N, E> {
+        return self.bidirectional_shortest_path_with_reverse(&self.reversed(), source, target);
+    }
+
+    /// Finds the shortest path from `source` to `target` like `bidirectional_shortest_path()`, using `reverse`
+    /// (the result of `reversed()` on this graph) for the backward search. Graphs built only with `add_edge()` are their own reverse.
+    pub fn bidirectional_shortest_path_with_reverse(&self, reverse: &Graph<N, E>, source: N, target: N) -> DijkstraResult<N, E> {
+        let mut forward: SearchFrontier<N, E> = SearchFrontier::new(source);
+        let mut backward: SearchFrontier<N, E> = SearchFrontier::new(target);
+        let mut best: Option<(E, N)> = (source == target).then_some((E::zero(), source));
+
+        // stop when no path through the nodes left in the queues can be shorter than the best one
+        while let (Some(forward_cost), Some(backward_cost)) = (forward.peek(), backward.peek()) {
+            if best.is_some_and(|(best_cost, _)| forward_cost.checked_add(backward_cost).is_none_or(|cost| cost >= best_cost)) {
+                break;
+            }
+
+            if forward_cost <= backward_cost {
+                forward.settle(self, &backward, &mut best);
+            } else {
+                backward.settle(reverse, &forward, &mut best);
+            }
+        }
+
+        let Some((cost, meeting_node)) = best else {
+            return DijkstraResult { cost: None, parents: None, path: None };
+        };
+
+        // walk from the meeting node back to source and forward to target, parents of the backward search point towards target
+        let mut path: Vec<(N, E)> = Vec::new();
+        let mut current_node = meeting_node;
+
+        while let Some(&(parent, weight)) = forward.parents.get(&current_node).filter(|_| current_node != source) {
+            path.push((current_node, weight));
+            current_node = parent;
+        }
+
+        path.reverse();
+        current_node = meeting_node;
+
+        while current_node != target {
+            let (next_node, weight) = backward.parents[&current_node];
+            path.push((next_node, weight));
+            current_node = next_node;
+        }
+
+        // both halves can pass the same node only through a cycle of zero weight, which is cut out
+        let mut parents = forward.parents;
+        let mut positions: HashMap<N, usize> = HashMap::from([(source, 0)]);
+        let mut nodes: Vec<(N, E)> = Vec::new();
+
+        for (node, weight) in path {
+            if let Some(&position) = positions.get(&node) {
+                for (removed, _) in nodes.drain(position..) {
+                    positions.remove(&removed);
+                }
+                continue;
+            }
+
+            positions.insert(node, nodes.len() + 1);
+            nodes.push((node, weight));
+        }
+
+        let mut previous_node = source;
+
+        for (node, weight) in nodes {
+            parents.insert(node, (previous_node, weight));
+            previous_node = node;
+        }
+
+        return self.dijkstra_result(source, target, cost, parents);
+    }
+}
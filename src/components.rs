@@ -0,0 +1,221 @@
+use crate::Graph;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// Components of a graph, returned by `Graph::connected_components()` and `Graph::strongly_connected_components()`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(
+    serialize = "N: serde::Serialize",
+    deserialize = "N: serde::Deserialize<'de> + Eq + Hash"
+)))]
+pub struct Components<N = u32> {
+    /// Nodes of every component. The index of a component is its id.
+    pub components: Vec<HashSet<N>>,
+    /// Map of every node and the id of its component.
+    pub labels: HashMap<N, usize>,
+}
+
+impl<N: Copy + Eq + Hash> Components<N> {
+    fn from_components(components: Vec<HashSet<N>>) -> Self {
+        let labels: HashMap<N, usize> = components
+            .iter()
+            .enumerate()
+            .flat_map(|(id, component)| component.iter().map(move |&node| (node, id)))
+            .collect();
+
+        return Components { components, labels };
+    }
+
+    /// Returns the number of components.
+    pub fn len(&self) -> usize {
+        return self.components.len();
+    }
+
+    /// Checks if there are no components, which is only the case for a graph without nodes.
+    pub fn is_empty(&self) -> bool {
+        return self.components.is_empty();
+    }
+
+    /// Returns the id of the component containing `node`, or `None` if it's not in the graph.
+    pub fn component_of(&self, node: N) -> Option<usize> {
+        return self.labels.get(&node).copied();
+    }
+
+    /// Returns nodes of the largest component, or `None` if there are no components. Ties are broken arbitrarily.
+    pub fn largest(&self) -> Option<&HashSet<N>> {
+        return self.components.iter().max_by_key(|component| component.len());
+    }
+
+    /// Checks if both nodes are in the graph and belong to the same component.
+    pub fn same_component(&self, node_a: N, node_b: N) -> bool {
+        return self.component_of(node_a).is_some_and(|component| self.component_of(node_b) == Some(component));
+    }
+}
+
+impl<N: Copy + Eq + Hash, E: Copy + PartialEq> Graph<N, E> {
+    /// Splits the graph into connected components, largest first. Directions of edges are ignored, so for directed graphs these are weakly connected components.
+    pub fn connected_components(&self) -> Components<N> {
+        let mut neighbours: HashMap<N, Vec<N>> = HashMap::with_capacity(self.nodes.len());
+
+        for (&node, adjacents) in &self.nodes {
+            for &(adjacent, _) in adjacents {
+                neighbours.entry(node).or_default().push(adjacent);
+                neighbours.entry(adjacent).or_default().push(node);
+            }
+        }
+
+        let mut components: Vec<HashSet<N>> = Vec::new();
+        let mut visited: HashSet<N> = HashSet::with_capacity(self.nodes.len());
+
+        for &start in self.nodes.keys() {
+            if !visited.insert(start) {
+                continue;
+            }
+
+            let mut component: HashSet<N> = HashSet::from([start]);
+            let mut queue: VecDeque<N> = VecDeque::from([start]);
+
+            while let Some(node) = queue.pop_front() {
+                for &adjacent in neighbours.get(&node).into_iter().flatten() {
+                    if self.nodes.contains_key(&adjacent) && visited.insert(adjacent) {
+                        component.insert(adjacent);
+                        queue.push_back(adjacent);
+                    }
+                }
+            }
+
+            components.push(component);
+        }
+
+        components.sort_by_key(|component| std::cmp::Reverse(component.len()));
+
+        return Components::from_components(components);
+    }
+
+    /// Checks if every node can be reached from every other one when directions of edges are ignored.
+    pub fn is_weakly_connected(&self) -> bool {
+        return self.component_count() <= 1;
+    }
+
+    /// Splits the graph into strongly connected components (Tarjan algorithm): sets of nodes which can all be reached from each other following directions of edges.
+    /// Components are in topological order: no edge leads from a component to one with a smaller id.
+    pub fn strongly_connected_components(&self) -> Components<N> {
+        let mut indices: HashMap<N, usize> = HashMap::with_capacity(self.nodes.len());
+        let mut low_links: HashMap<N, usize> = HashMap::with_capacity(self.nodes.len());
+        let mut stack: Vec<N> = Vec::new();
+        let mut on_stack: HashSet<N> = HashSet::new();
+        let mut components: Vec<HashSet<N>> = Vec::new();
+
+        for &start in self.nodes.keys() {
+            if indices.contains_key(&start) {
+                continue;
+            }
+
+            indices.insert(start, indices.len());
+            low_links.insert(start, indices[&start]);
+            stack.push(start);
+            on_stack.insert(start);
+
+            let mut calls = vec![(start, self.nodes[&start].iter())];
+
+            while let Some((node, adjacents)) = calls.last_mut() {
+                let node = *node;
+
+                if let Some(&(adjacent, _)) = adjacents.next() {
+                    if !self.nodes.contains_key(&adjacent) {
+                        continue;
+                    }
+
+                    if !indices.contains_key(&adjacent) {
+                        indices.insert(adjacent, indices.len());
+                        low_links.insert(adjacent, indices[&adjacent]);
+                        stack.push(adjacent);
+                        on_stack.insert(adjacent);
+                        calls.push((adjacent, self.nodes[&adjacent].iter()));
+                    } else if on_stack.contains(&adjacent) {
+                        low_links.insert(node, low_links[&node].min(indices[&adjacent]));
+                    }
+
+                    continue;
+                }
+
+                calls.pop();
+
+                if let Some(&(parent, _)) = calls.last() {
+                    low_links.insert(parent, low_links[&parent].min(low_links[&node]));
+                }
+
+                if low_links[&node] == indices[&node] {
+                    let mut component: HashSet<N> = HashSet::new();
+
+                    while let Some(member) = stack.pop() {
+                        on_stack.remove(&member);
+                        component.insert(member);
+
+                        if member == node {
+                            break;
+                        }
+                    }
+
+                    components.push(component);
+                }
+            }
+        }
+
+        components.reverse();
+
+        return Components::from_components(components);
+    }
+
+    /// Checks if every node can be reached from every other one following directions of edges.
+    pub fn is_strongly_connected(&self) -> bool {
+        return self.strongly_connected_components().len() <= 1;
+    }
+
+    /// Collapses every strongly connected component into one node, which gives a directed acyclic graph.
+    /// Node `i` of the returned graph is component `i` of the returned `Components`, which maps nodes of both graphs to each other.
+    /// Every edge between different components is kept with its weight, edges inside components are dropped.
+    pub fn condensation(&self) -> (Graph<usize, E>, Components<N>) {
+        let components = self.strongly_connected_components();
+        let mut condensation: Graph<usize, E> = Graph::default();
+
+        for id in 0..components.len() {
+            condensation.add_node(id);
+        }
+
+        for (node, adjacents) in &self.nodes {
+            let source = components.labels[node];
+
+            for (adjacent, weight) in adjacents {
+                if let Some(&target) = components.labels.get(adjacent).filter(|&&target| target != source) {
+                    condensation.add_edge_directed(source, target, *weight);
+                }
+            }
+        }
+
+        return (condensation, components);
+    }
+
+    /// Returns the number of connected components, ignoring directions of edges.
+    pub fn component_count(&self) -> usize {
+        return self.connected_components().len();
+    }
+
+    /// Returns nodes of the largest connected component, ignoring directions of edges. Returns an empty set for a graph without nodes.
+    pub fn largest_component(&self) -> HashSet<N> {
+        return self.connected_components().components.into_iter().next().unwrap_or_default();
+    }
+
+    /// Drops every node outside of the largest connected component, together with its edges and coordinates.
+    pub fn retain_largest_component(&mut self) {
+        let largest = self.largest_component();
+
+        self.nodes.retain(|node, _| largest.contains(node));
+        self.coordinates.retain(|node, _| largest.contains(node));
+
+        for adjacents in self.nodes.values_mut() {
+            adjacents.retain(|(adjacent, _)| largest.contains(adjacent));
+        }
+    }
+}
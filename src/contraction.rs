@@ -0,0 +1,338 @@
+use crate::{compare_weights, DijkstraResult, DijkstraState, Graph, GraphError, Path, Weight};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+/// Number of nodes a witness search may settle before giving up. Giving up only adds extra shortcuts, paths stay the shortest.
+const WITNESS_SEARCH_LIMIT: usize = 500;
+
+/// Edge of a contraction hierarchy: adjacent node, weight and the node a shortcut goes through, `None` for edges of the original graph.
+type HierarchyEdge<E> = (usize, E, Option<usize>);
+
+/// Contraction Hierarchies index, built once from a graph by `Graph::contraction_hierarchy()` to answer shortest path queries fast.
+/// Nodes are ordered by importance and every node is contracted in turn, adding shortcut edges which keep the shortest paths between the remaining nodes.
+/// A query then runs two small Dijkstra searches which only go up the hierarchy. The index doesn't follow later changes of the graph.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(
+    serialize = "N: serde::Serialize, E: serde::Serialize",
+    deserialize = "N: serde::Deserialize<'de> + Eq + Hash, E: serde::Deserialize<'de>"
+)))]
+pub struct ContractionHierarchy<N = u32, E = i32> {
+    /// Nodes in the order of contraction, the index of a node is its rank.
+    nodes: Vec<N>,
+    /// Map of a node and its rank.
+    ranks: HashMap<N, usize>,
+    /// Edges going from a node to nodes of higher rank.
+    upward: Vec<Vec<HierarchyEdge<E>>>,
+    /// Edges coming to a node from nodes of higher rank, stored with the node they come from.
+    downward: Vec<Vec<HierarchyEdge<E>>>,
+}
+
+/// Remaining graph while nodes are being contracted. Edges are stored with the node a shortcut goes through.
+struct Contractor<E> {
+    out_edges: Vec<HashMap<usize, (E, Option<usize>)>>,
+    in_edges: Vec<HashMap<usize, (E, Option<usize>)>>,
+    deleted_neighbors: Vec<usize>,
+}
+
+impl<E: Weight> Contractor<E> {
+    /// Returns shortcuts `(source, target, weight)` needed to keep shortest paths if `node` is contracted.
+    fn shortcuts(&self, node: usize) -> Vec<(usize, usize, E)> {
+        let mut shortcuts: Vec<(usize, usize, E)> = Vec::new();
+
+        for (&source, &(in_weight, _)) in &self.in_edges[node] {
+            let candidates: Vec<(usize, E)> = self.out_edges[node].iter()
+                .filter(|(&target, _)| target != source)
+                .filter_map(|(&target, &(out_weight, _))| Some((target, in_weight.checked_add(out_weight)?)))
+                .collect();
+
+            let Some(limit) = candidates.iter().map(|&(_, cost)| cost).max_by(compare_weights) else {
+                continue;
+            };
+
+            let distances = self.witness_search(source, node, limit);
+
+            for (target, cost) in candidates {
+                if distances.get(&target).is_none_or(|&distance| distance > cost) {
+                    shortcuts.push((source, target, cost));
+                }
+            }
+        }
+
+        return shortcuts;
+    }
+
+    /// Runs Dijkstra algorithm from `source` in the remaining graph without `ignored`, up to the distance `limit`.
+    fn witness_search(&self, source: usize, ignored: usize, limit: E) -> HashMap<usize, E> {
+        let mut queue: BinaryHeap<DijkstraState<usize, E>> = BinaryHeap::from([DijkstraState { node: source, cost: E::zero() }]);
+        let mut distances: HashMap<usize, E> = HashMap::from([(source, E::zero())]);
+        let mut settled = 0;
+
+        while let Some(DijkstraState { node, cost }) = queue.pop() {
+            if cost > limit || settled >= WITNESS_SEARCH_LIMIT {
+                break;
+            }
+            if cost > distances[&node] {
+                continue;
+            }
+
+            settled += 1;
+
+            for (&adjacent, &(weight, _)) in &self.out_edges[node] {
+                let Some(next_cost) = cost.checked_add(weight) else {
+                    continue;
+                };
+
+                if adjacent != ignored && distances.get(&adjacent).is_none_or(|&distance| next_cost < distance) {
+                    queue.push(DijkstraState { node: adjacent, cost: next_cost });
+                    distances.insert(adjacent, next_cost);
+                }
+            }
+        }
+
+        return distances;
+    }
+
+    /// Estimates how much contracting `node` grows the graph, nodes with lower priority are contracted first.
+    fn priority(&self, node: usize) -> isize {
+        let removed_edges = self.in_edges[node].len() + self.out_edges[node].len();
+
+        return self.shortcuts(node).len() as isize - removed_edges as isize + self.deleted_neighbors[node] as isize;
+    }
+
+    /// Removes `node` from the remaining graph, adding shortcuts between its neighbors.
+    fn contract(&mut self, node: usize) {
+        let shortcuts = self.shortcuts(node);
+
+        for source in std::mem::take(&mut self.in_edges[node]).into_keys() {
+            self.out_edges[source].remove(&node);
+            self.deleted_neighbors[source] += 1;
+        }
+        for target in std::mem::take(&mut self.out_edges[node]).into_keys() {
+            self.in_edges[target].remove(&node);
+            self.deleted_neighbors[target] += 1;
+        }
+
+        for (source, target, weight) in shortcuts {
+            if self.out_edges[source].get(&target).is_none_or(|&(current, _)| weight < current) {
+                self.out_edges[source].insert(target, (weight, Some(node)));
+                self.in_edges[target].insert(source, (weight, Some(node)));
+            }
+        }
+    }
+}
+
+impl<N: Copy + Eq + Hash, E: Weight> Graph<N, E> {
+    /// Builds a Contraction Hierarchies index of the graph, which answers `shortest_path()` queries much faster than Dijkstra algorithm.
+    /// Preprocessing takes a while, so it pays off when many queries are run on a graph which doesn't change.
+    /// Returns `GraphError::InvalidInput` if the graph has an edge of negative weight.
+    pub fn contraction_hierarchy(&self) -> Result<ContractionHierarchy<N, E>, GraphError<N>> {
+        let nodes: Vec<N> = self.nodes.keys().copied().collect();
+        let indices: HashMap<N, usize> = nodes.iter().enumerate().map(|(index, &node)| (node, index)).collect();
+        let count = nodes.len();
+
+        let mut contractor = Contractor {
+            out_edges: vec![HashMap::new(); count],
+            in_edges: vec![HashMap::new(); count],
+            deleted_neighbors: vec![0; count],
+        };
+
+        for (source, adjacents) in &self.nodes {
+            for &(target, weight) in adjacents {
+                if weight < E::zero() {
+                    return Err(GraphError::InvalidInput(String::from("contraction hierarchies require edges of non-negative weight")));
+                }
+
+                let (source, Some(&target)) = (indices[source], indices.get(&target)) else {
+                    continue;
+                };
+
+                if contractor.out_edges[source].get(&target).is_none_or(|&(current, _)| weight < current) {
+                    contractor.out_edges[source].insert(target, (weight, None));
+                    contractor.in_edges[target].insert(source, (weight, None));
+                }
+            }
+        }
+
+        let mut queue: BinaryHeap<Reverse<(isize, usize)>> = (0..count).map(|node| Reverse((contractor.priority(node), node))).collect();
+        let mut order: Vec<usize> = Vec::with_capacity(count);
+        let mut upward: Vec<Vec<HierarchyEdge<E>>> = vec![Vec::new(); count];
+        let mut downward: Vec<Vec<HierarchyEdge<E>>> = vec![Vec::new(); count];
+
+        while let Some(Reverse((_, node))) = queue.pop() {
+            // priorities change as neighbors are contracted, so they are updated lazily
+            let priority = contractor.priority(node);
+
+            if queue.peek().is_some_and(|&Reverse((next_priority, _))| priority > next_priority) {
+                queue.push(Reverse((priority, node)));
+                continue;
+            }
+
+            upward[node] = contractor.out_edges[node].iter().map(|(&target, &(weight, middle))| (target, weight, middle)).collect();
+            downward[node] = contractor.in_edges[node].iter().map(|(&source, &(weight, middle))| (source, weight, middle)).collect();
+
+            contractor.contract(node);
+            order.push(node);
+        }
+
+        let mut ranks: Vec<usize> = vec![0; count];
+        for (rank, &node) in order.iter().enumerate() {
+            ranks[node] = rank;
+        }
+
+        let relabel = |edges: &Vec<HierarchyEdge<E>>| -> Vec<HierarchyEdge<E>> {
+            return edges.iter().map(|&(node, weight, middle)| (ranks[node], weight, middle.map(|middle| ranks[middle]))).collect();
+        };
+
+        return Ok(ContractionHierarchy {
+            nodes: order.iter().map(|&node| nodes[node]).collect(),
+            ranks: order.iter().enumerate().map(|(rank, &node)| (nodes[node], rank)).collect(),
+            upward: order.iter().map(|&node| relabel(&upward[node])).collect(),
+            downward: order.iter().map(|&node| relabel(&downward[node])).collect(),
+        });
+    }
+}
+
+impl<N: Copy + Eq + Hash, E: Weight> ContractionHierarchy<N, E> {
+    /// Finds the shortest path from `source` to `target`. Returns the same cost and path as `Graph::shortest_path()` on the indexed graph,
+    /// with shortcuts unpacked into edges of the graph. Unknown nodes are reported as unreachable.
+    pub fn shortest_path(&self, source: N, target: N) -> DijkstraResult<N, E> {
+        let no_path = DijkstraResult { cost: None, parents: None, path: None };
+
+        let (Some(&source), Some(&target)) = (self.ranks.get(&source), self.ranks.get(&target)) else {
+            return no_path;
+        };
+
+        let (forward_distances, forward_parents) = self.upward_search(source, &self.upward);
+        let (backward_distances, backward_parents) = self.upward_search(target, &self.downward);
+
+        let meeting = forward_distances.iter()
+            .filter_map(|(node, &forward)| Some((*node, forward.checked_add(*backward_distances.get(node)?)?)))
+            .min_by(|&(node_a, cost_a), &(node_b, cost_b)| compare_weights(&cost_a, &cost_b).then(node_a.cmp(&node_b)));
+
+        let Some((meeting_node, cost)) = meeting else {
+            return no_path;
+        };
+
+        // edges of the hierarchy on the path, parents of the backward search point towards target
+        let mut edges: Vec<(usize, usize, E, Option<usize>)> = Vec::new();
+        let mut current_node = meeting_node;
+
+        while let Some(&(parent, weight, middle)) = forward_parents.get(&current_node) {
+            edges.push((parent, current_node, weight, middle));
+            current_node = parent;
+        }
+
+        edges.reverse();
+        current_node = meeting_node;
+
+        while let Some(&(next_node, weight, middle)) = backward_parents.get(&current_node) {
+            edges.push((current_node, next_node, weight, middle));
+            current_node = next_node;
+        }
+
+        let mut nodes: Vec<N> = vec![self.nodes[source]];
+        let mut weights: Vec<E> = Vec::new();
+
+        for (edge_source, edge_target, weight, middle) in edges {
+            for (node, weight) in self.unpack(edge_source, edge_target, weight, middle) {
+                nodes.push(self.nodes[node]);
+                weights.push(weight);
+            }
+        }
+
+        let parents: HashMap<N, N> = nodes.windows(2).map(|pair| (pair[1], pair[0])).collect();
+
+        return DijkstraResult { cost: Some(cost), parents: Some(parents), path: Some(Path { nodes, weights, cost }) };
+    }
+
+    /// Finds the shortest path from `source` to `target` like `shortest_path()`.
+    /// Returns `GraphError::UnknownNode` if `source` or `target` was not present in the indexed graph and `GraphError::NoPath` if `target` can't be reached.
+    pub fn try_shortest_path(&self, source: N, target: N) -> Result<DijkstraResult<N, E>, GraphError<N>> {
+        for node in [source, target] {
+            if !self.ranks.contains_key(&node) {
+                return Err(GraphError::UnknownNode(node));
+            }
+        }
+
+        let result = self.shortest_path(source, target);
+
+        if result.cost.is_none() {
+            return Err(GraphError::NoPath { source, target });
+        }
+
+        return Ok(result);
+    }
+
+    /// Returns the number of nodes in the index.
+    pub fn len(&self) -> usize {
+        return self.nodes.len();
+    }
+
+    /// Checks if the index has no nodes.
+    pub fn is_empty(&self) -> bool {
+        return self.nodes.is_empty();
+    }
+
+    /// Returns the number of shortcut edges added while building the index.
+    pub fn shortcut_count(&self) -> usize {
+        return self.upward.iter().chain(&self.downward).flatten().filter(|(_, _, middle)| middle.is_some()).count();
+    }
+
+    /// Runs Dijkstra algorithm from `start` over `edges` going up the hierarchy. Returns distances and a map of a node
+    /// and the node it was reached from together with the edge between them.
+    fn upward_search(&self, start: usize, edges: &[Vec<HierarchyEdge<E>>]) -> (HashMap<usize, E>, HashMap<usize, HierarchyEdge<E>>) {
+        let mut queue: BinaryHeap<DijkstraState<usize, E>> = BinaryHeap::from([DijkstraState { node: start, cost: E::zero() }]);
+        let mut distances: HashMap<usize, E> = HashMap::from([(start, E::zero())]);
+        let mut parents: HashMap<usize, HierarchyEdge<E>> = HashMap::new();
+
+        while let Some(DijkstraState { node, cost }) = queue.pop() {
+            if cost > distances[&node] {
+                continue;
+            }
+
+            for &(adjacent, weight, middle) in &edges[node] {
+                let Some(next_cost) = cost.checked_add(weight) else {
+                    continue;
+                };
+
+                if distances.get(&adjacent).is_none_or(|&distance| next_cost < distance) {
+                    queue.push(DijkstraState { node: adjacent, cost: next_cost });
+                    distances.insert(adjacent, next_cost);
+                    parents.insert(adjacent, (node, weight, middle));
+                }
+            }
+        }
+
+        return (distances, parents);
+    }
+
+    /// Replaces an edge of the hierarchy with the edges of the graph it stands for. Returns the nodes after `source` with weights of the edges leading to them.
+    fn unpack(&self, source: usize, target: usize, weight: E, middle: Option<usize>) -> Vec<(usize, E)> {
+        let mut unpacked: Vec<(usize, E)> = Vec::new();
+        let mut stack: Vec<(usize, usize, E, Option<usize>)> = vec![(source, target, weight, middle)];
+
+        while let Some((source, target, weight, middle)) = stack.pop() {
+            let Some(middle) = middle else {
+                unpacked.push((target, weight));
+                continue;
+            };
+
+            // the middle node was contracted before both ends, so the second half goes up from it and the first half comes down to it
+            let (second_weight, second_middle) = Self::find_edge(&self.upward[middle], target);
+            let (first_weight, first_middle) = Self::find_edge(&self.downward[middle], source);
+
+            stack.push((middle, target, second_weight, second_middle));
+            stack.push((source, middle, first_weight, first_middle));
+        }
+
+        return unpacked;
+    }
+
+    fn find_edge(edges: &[HierarchyEdge<E>], node: usize) -> (E, Option<usize>) {
+        let &(_, weight, middle) = edges.iter().find(|&&(adjacent, _, _)| adjacent == node).expect("shortcut refers to a missing edge");
+
+        return (weight, middle);
+    }
+}
@@ -0,0 +1,162 @@
+use crate::{Graph, GraphError};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+impl<N: Copy + Eq + Hash, E: Copy + PartialEq> Graph<N, E> {
+    /// Checks if the graph contains a cycle following directions of edges. An edge added with `add_edge()` goes both ways, so it's a cycle of two nodes.
+    pub fn has_cycle(&self) -> bool {
+        return self.find_cycle().is_some();
+    }
+
+    /// Checks if the graph contains a cycle when directions of edges are ignored.
+    /// Edges between the same two nodes count as one, so edges added with `add_edge()` are not cycles, but an edge from a node to itself is.
+    pub fn has_cycle_undirected(&self) -> bool {
+        let mut edges: HashSet<(N, N)> = HashSet::new();
+
+        for (&node, adjacents) in &self.nodes {
+            for &(adjacent, _) in adjacents {
+                if adjacent == node {
+                    return true;
+                }
+
+                if self.nodes.contains_key(&adjacent) && !edges.contains(&(adjacent, node)) {
+                    edges.insert((node, adjacent));
+                }
+            }
+        }
+
+        // a graph without cycles is a forest, which has exactly one edge less than nodes in every component
+        return edges.len() > self.nodes.len() - self.component_count();
+    }
+
+    /// Returns nodes of one cycle following directions of edges, or `None` if there is none.
+    /// The last node has an edge to the first one.
+    pub fn find_cycle(&self) -> Option<Vec<N>> {
+        if let Err(GraphError::Cycle(cycle)) = self.topological_sort_dfs() {
+            return Some(cycle);
+        }
+
+        return None;
+    }
+
+    /// Lists elementary cycles of the graph following directions of edges (Johnson algorithm), stopping after `limit` cycles.
+    /// An elementary cycle doesn't visit any node twice. Every cycle is listed once, the last node has an edge to the first one.
+    /// The number of cycles can grow exponentially with the size of the graph, so keep `limit` reasonable.
+    pub fn elementary_cycles(&self, limit: usize) -> Vec<Vec<N>> {
+        let nodes: Vec<N> = self.nodes.keys().copied().collect();
+        let indices: HashMap<N, usize> = nodes.iter().enumerate().map(|(index, &node)| (node, index)).collect();
+
+        let adjacents: Vec<Vec<usize>> = nodes
+            .iter()
+            .map(|node| {
+                let targets: HashSet<usize> = self.nodes[node].iter().filter_map(|(adjacent, _)| indices.get(adjacent).copied()).collect();
+                return targets.into_iter().collect();
+            })
+            .collect();
+        let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+
+        for (node, targets) in adjacents.iter().enumerate() {
+            for &target in targets {
+                predecessors[target].push(node);
+            }
+        }
+
+        let mut cycles: Vec<Vec<N>> = Vec::new();
+        let mut blocked: Vec<bool> = vec![false; nodes.len()];
+        let mut blocked_by: Vec<HashSet<usize>> = vec![HashSet::new(); nodes.len()];
+
+        // every cycle is found from its node with the smallest index, in the subgraph of nodes with an index not smaller than that
+        for start in 0..nodes.len() {
+            if cycles.len() >= limit {
+                break;
+            }
+
+            let forward = reachable_from(start, &adjacents, start);
+            let component: HashSet<usize> = reachable_from(start, &predecessors, start).intersection(&forward).copied().collect();
+
+            for &node in &component {
+                blocked[node] = false;
+                blocked_by[node].clear();
+            }
+
+            let mut path: Vec<usize> = vec![start];
+            // node on the path, index of its next edge to explore and whether a cycle was found through it
+            let mut frames: Vec<(usize, usize, bool)> = vec![(start, 0, false)];
+            blocked[start] = true;
+
+            while let Some((node, next_edge, found)) = frames.last_mut() {
+                let node = *node;
+
+                if let Some(&adjacent) = adjacents[node].get(*next_edge) {
+                    *next_edge += 1;
+
+                    if !component.contains(&adjacent) {
+                        continue;
+                    }
+
+                    if adjacent == start {
+                        *found = true;
+                        cycles.push(path.iter().map(|&index| nodes[index]).collect());
+
+                        if cycles.len() >= limit {
+                            break;
+                        }
+                    } else if !blocked[adjacent] {
+                        blocked[adjacent] = true;
+                        path.push(adjacent);
+                        frames.push((adjacent, 0, false));
+                    }
+
+                    continue;
+                }
+
+                let found = *found;
+
+                frames.pop();
+                path.pop();
+
+                if found {
+                    unblock(node, &mut blocked, &mut blocked_by);
+                } else {
+                    for &adjacent in adjacents[node].iter().filter(|adjacent| component.contains(adjacent)) {
+                        blocked_by[adjacent].insert(node);
+                    }
+                }
+
+                if let Some((_, _, parent_found)) = frames.last_mut() {
+                    *parent_found |= found;
+                }
+            }
+        }
+
+        return cycles;
+    }
+}
+
+/// Collects nodes reachable from `start` through nodes with an index not smaller than `min_index`.
+fn reachable_from(start: usize, adjacents: &[Vec<usize>], min_index: usize) -> HashSet<usize> {
+    let mut reachable: HashSet<usize> = HashSet::from([start]);
+    let mut queue: VecDeque<usize> = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        for &adjacent in &adjacents[node] {
+            if adjacent >= min_index && reachable.insert(adjacent) {
+                queue.push_back(adjacent);
+            }
+        }
+    }
+
+    return reachable;
+}
+
+/// Unblocks `node` and every node which was blocked because of it.
+fn unblock(node: usize, blocked: &mut [bool], blocked_by: &mut [HashSet<usize>]) {
+    let mut stack: Vec<usize> = vec![node];
+
+    while let Some(current_node) = stack.pop() {
+        if blocked[current_node] {
+            blocked[current_node] = false;
+            stack.extend(blocked_by[current_node].drain());
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use crate::{compare_weights, path_from_parents, DfsEvent, Graph, GraphError, Path, Weight};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+impl<N: Copy + Eq + Hash, E: Copy + PartialEq> Graph<N, E> {
+    /// Orders nodes so that every edge leads from an earlier node to a later one (Kahn algorithm).
+    /// Returns `GraphError::Cycle` with one of the cycles if the graph is not a directed acyclic graph.
+    pub fn topological_sort(&self) -> Result<Vec<N>, GraphError<N>> {
+        let mut in_degrees: HashMap<N, usize> = self.nodes.keys().map(|&node| (node, 0)).collect();
+
+        for adjacents in self.nodes.values() {
+            for (adjacent, _) in adjacents {
+                if let Some(in_degree) = in_degrees.get_mut(adjacent) {
+                    *in_degree += 1;
+                }
+            }
+        }
+
+        let mut queue: VecDeque<N> = in_degrees.iter().filter(|&(_, &in_degree)| in_degree == 0).map(|(&node, _)| node).collect();
+        let mut order: Vec<N> = Vec::with_capacity(self.nodes.len());
+
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+
+            for (adjacent, _) in &self.nodes[&node] {
+                if let Some(in_degree) = in_degrees.get_mut(adjacent) {
+                    *in_degree -= 1;
+
+                    if *in_degree == 0 {
+                        queue.push_back(*adjacent);
+                    }
+                }
+            }
+        }
+
+        if order.len() < self.nodes.len() {
+            // nodes left over are on a cycle or behind one, the DFS-based sort reports the cycle itself
+            return self.topological_sort_dfs();
+        }
+
+        return Ok(order);
+    }
+
+    /// Same as `topological_sort()`, but orders nodes by reversed finish time of a depth-first search.
+    pub fn topological_sort_dfs(&self) -> Result<Vec<N>, GraphError<N>> {
+        let mut order: Vec<N> = Vec::with_capacity(self.nodes.len());
+        let mut roots = self.nodes.keys().copied();
+
+        let Some(first) = roots.next() else {
+            return Ok(order);
+        };
+
+        let mut dfs = self.dfs(first);
+
+        for root in roots {
+            dfs.add_root(root);
+        }
+
+        while let Some(event) = dfs.next_event() {
+            match event {
+                DfsEvent::Finish(node) if self.has_node(node) => order.push(node),
+                DfsEvent::BackEdge(source, target, _) => {
+                    let mut cycle: Vec<N> = vec![source];
+
+                    while cycle[cycle.len() - 1] != target {
+                        cycle.push(dfs.parents()[&cycle[cycle.len() - 1]]);
+                    }
+
+                    cycle.reverse();
+
+                    return Err(GraphError::Cycle(cycle));
+                }
+                _ => {}
+            }
+        }
+
+        order.reverse();
+
+        return Ok(order);
+    }
+
+    /// Returns a copy of a directed acyclic graph without redundant edges: an edge from `a` to `b` is dropped if `b` can also be reached from `a` through other nodes.
+    /// Every node can still reach the same nodes as before. Returns `GraphError::Cycle` if the graph is not a directed acyclic graph.
+    pub fn transitive_reduction(&self) -> Result<Self, GraphError<N>> {
+        let order = self.topological_sort()?;
+
+        let mut reduction = Self { nodes: HashMap::with_capacity(self.nodes.len()), coordinates: self.coordinates.clone() };
+        let mut descendants: HashMap<N, HashSet<N>> = HashMap::with_capacity(self.nodes.len());
+
+        for &node in order.iter().rev() {
+            // nodes reachable through at least two edges
+            let mut reachable: HashSet<N> = HashSet::new();
+
+            for (adjacent, _) in &self.nodes[&node] {
+                reachable.extend(descendants.get(adjacent).into_iter().flatten());
+            }
+
+            let adjacents: Vec<(N, E)> = self.nodes[&node].iter().filter(|(adjacent, _)| !reachable.contains(adjacent)).copied().collect();
+
+            reachable.extend(self.nodes[&node].iter().map(|&(adjacent, _)| adjacent));
+            descendants.insert(node, reachable);
+            reduction.nodes.insert(node, adjacents);
+        }
+
+        return Ok(reduction);
+    }
+}
+
+impl<N: Copy + Eq + Hash, E: Weight> Graph<N, E> {
+    /// Finds the path with the largest total weight from `source` to `target` in a directed acyclic graph. Returns `Ok(None)` if there is no path.
+    /// Returns `GraphError::UnknownNode` if either node is not present and `GraphError::Cycle` if the graph is not a directed acyclic graph.
+    pub fn longest_path(&self, source: N, target: N) -> Result<Option<Path<N, E>>, GraphError<N>> {
+        self.check_node(source)?;
+        self.check_node(target)?;
+
+        let order = self.topological_sort()?;
+        let (distances, parents) = self.longest_distances(&order, Some(source));
+
+        if !distances.contains_key(&target) {
+            return Ok(None);
+        }
+
+        return Ok(path_from_parents(source, target, &parents));
+    }
+
+    /// Finds the path with the largest total weight in a directed acyclic graph, e.g. the critical path of a schedule where nodes are tasks,
+    /// edges are dependencies and weights are durations. Ties are broken arbitrarily. Returns `Ok(None)` for a graph without nodes.
+    /// Returns `GraphError::Cycle` if the graph is not a directed acyclic graph.
+    pub fn critical_path(&self) -> Result<Option<Path<N, E>>, GraphError<N>> {
+        let order = self.topological_sort()?;
+        let (distances, parents) = self.longest_distances(&order, None);
+
+        let Some((&target, _)) = distances.iter().max_by(|(_, a), (_, b)| compare_weights(*a, *b)) else {
+            return Ok(None);
+        };
+
+        let mut source = target;
+        while let Some(&(parent, _)) = parents.get(&source) {
+            source = parent;
+        }
+
+        return Ok(path_from_parents(source, target, &parents));
+    }
+
+    /// Computes the largest distances from `source` (or from every node if `None`) to every reachable node, visiting nodes in topological `order`.
+    fn longest_distances(&self, order: &[N], source: Option<N>) -> (HashMap<N, E>, HashMap<N, (N, E)>) {
+        let mut distances: HashMap<N, E> = match source {
+            Some(source) => HashMap::from([(source, E::zero())]),
+            None => order.iter().map(|&node| (node, E::zero())).collect(),
+        };
+        let mut parents: HashMap<N, (N, E)> = HashMap::new();
+
+        for &node in order {
+            let Some(&distance) = distances.get(&node) else {
+                continue;
+            };
+
+            for &(adjacent, weight) in &self.nodes[&node] {
+                if !self.nodes.contains_key(&adjacent) {
+                    continue;
+                }
+
+                let Some(candidate) = distance.checked_add(weight) else {
+                    continue;
+                };
+
+                if distances.get(&adjacent).is_none_or(|&current| candidate > current) {
+                    distances.insert(adjacent, candidate);
+                    parents.insert(adjacent, (node, weight));
+                }
+            }
+        }
+
+        return (distances, parents);
+    }
+}
@@ -0,0 +1,418 @@
+use crate::{Graph, ParseError};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::hash::Hash;
+use std::io;
+use std::io::{Read, Write};
+use std::str::FromStr;
+
+const HIGHLIGHT: &str = "color=red, penwidth=2";
+
+#[derive(Clone, PartialEq, Debug)]
+enum Token {
+    Id(String),
+    Edge { directed: bool },
+    OpenBrace,
+    CloseBrace,
+    OpenBracket,
+    CloseBracket,
+    Equals,
+    Semicolon,
+    Comma,
+    Colon,
+}
+
+impl<N: Copy + Eq + Hash, E: Copy + PartialEq> Graph<N, E> {
+    /// Exports a graph into Graphviz DOT format.
+    /// If every edge exists in both directions with the same weight (see `add_edge()`) the output is an undirected `graph`,
+    /// otherwise it is a `digraph` where edges existing in both directions are drawn with `dir=both`. Weights are written as edge labels.
+    pub fn to_dot(&self) -> String
+    where
+        N: Display,
+        E: Display,
+    {
+        return self.to_dot_with_path([]);
+    }
+
+    /// Exports a graph into DOT format, highlighting nodes and edges of `path`, e.g. one returned by `restore_path()`.
+    pub fn to_dot_with_path(&self, path: impl IntoIterator<Item = N>) -> String
+    where
+        N: Display,
+        E: Display,
+    {
+        let mut buffer: Vec<u8> = Vec::new();
+        self.write_dot_with_path(&mut buffer, path).unwrap();
+
+        return String::from_utf8(buffer).unwrap();
+    }
+
+    /// Exports a graph into DOT format and writes it to `writer`. Produces the same output as `to_dot()`.
+    pub fn write_dot<W: Write>(&self, writer: W) -> io::Result<()>
+    where
+        N: Display,
+        E: Display,
+    {
+        return self.write_dot_with_path(writer, []);
+    }
+
+    /// Exports a graph into DOT format, highlighting nodes and edges of `path`, and writes it to `writer`.
+    pub fn write_dot_with_path<W: Write>(&self, mut writer: W, path: impl IntoIterator<Item = N>) -> io::Result<()>
+    where
+        N: Display,
+        E: Display,
+    {
+        let path: Vec<N> = path.into_iter().collect();
+        let path_nodes: HashSet<N> = path.iter().copied().collect();
+        let path_edges: HashSet<(N, N)> = path.windows(2).map(|pair| (pair[0], pair[1])).collect();
+
+        let edges = self.edges_by_direction();
+
+        let is_undirected = edges.iter().all(|&(_, _, _, is_symmetric)| is_symmetric);
+        let (kind, operator) = if is_undirected { ("graph", "--") } else { ("digraph", "->") };
+
+        writeln!(writer, "{} {{", kind)?;
+
+        for node in self.nodes.keys() {
+            if path_nodes.contains(node) {
+                writeln!(writer, "    {} [{}];", quote(node), HIGHLIGHT)?;
+            } else {
+                writeln!(writer, "    {};", quote(node))?;
+            }
+        }
+
+        for (source, target, weight, is_symmetric) in edges {
+            let mut attributes = format!("label={}", quote(&weight));
+
+            if is_symmetric && !is_undirected {
+                attributes.push_str(", dir=both");
+            }
+
+            if path_edges.contains(&(source, target)) || (is_symmetric && path_edges.contains(&(target, source))) {
+                attributes.push_str(", ");
+                attributes.push_str(HIGHLIGHT);
+            }
+
+            writeln!(writer, "    {} {} {} [{}];", quote(&source), operator, quote(&target), attributes)?;
+        }
+
+        writeln!(writer, "}}")?;
+
+        return Ok(());
+    }
+
+    /// Imports a graph from a simple DOT file.
+    /// In a `graph` every edge is added with `add_edge()`, in a `digraph` edges are added with `add_edge_directed()`
+    /// unless they have the `dir=both` attribute. Weights are read from the `weight` attribute, falling back to `label`,
+    /// then to the attributes of the last `edge [...]` statement and finally to `E::default()`.
+    /// Nodes which appear only in edge statements are added to the graph. Subgraphs, ports and HTML labels are not supported.
+    pub fn from_dot(dot: &str) -> Result<Self, ParseError>
+    where
+        N: FromStr,
+        E: FromStr + Default,
+    {
+        return Self::from_dot_with_default_weight(dot, E::default());
+    }
+
+    /// Imports a graph from a simple DOT file like `from_dot()`, using `default_weight` for edges without a weight or label.
+    pub fn from_dot_with_default_weight(dot: &str, default_weight: E) -> Result<Self, ParseError>
+    where
+        N: FromStr,
+        E: FromStr,
+    {
+        let tokens = tokenize(dot)?;
+        let mut parser = DotParser { tokens, position: 0 };
+
+        return parser.parse(default_weight);
+    }
+
+    /// Imports a graph from a simple DOT file, reading it from `reader`. See `from_dot()`.
+    pub fn read_dot<R: Read>(mut reader: R) -> Result<Self, ParseError>
+    where
+        N: FromStr,
+        E: FromStr + Default,
+    {
+        let mut dot = String::new();
+        reader.read_to_string(&mut dot)?;
+
+        return Self::from_dot(&dot);
+    }
+}
+
+fn quote(value: &impl Display) -> String {
+    return format!("\"{}\"", value.to_string().replace('\\', "\\\\").replace('"', "\\\""));
+}
+
+fn syntax_error(line: usize, message: impl Into<String>) -> ParseError {
+    return ParseError::Syntax { line, message: message.into() };
+}
+
+fn tokenize(dot: &str) -> Result<Vec<(Token, usize)>, ParseError> {
+    let mut tokens: Vec<(Token, usize)> = Vec::new();
+    let mut chars = dot.chars().peekable();
+    let mut line = 1;
+    let mut at_line_start = true;
+
+    while let Some(char) = chars.next() {
+        match char {
+            '\n' => {
+                line += 1;
+                at_line_start = true;
+                continue;
+            }
+            _ if char.is_whitespace() => continue,
+            '#' if at_line_start => {
+                while chars.next_if(|&next| next != '\n').is_some() {}
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                while chars.next_if(|&next| next != '\n').is_some() {}
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let start = line;
+                loop {
+                    match chars.next() {
+                        Some('*') if chars.peek() == Some(&'/') => {
+                            chars.next();
+                            break;
+                        }
+                        Some('\n') => line += 1,
+                        Some(_) => {}
+                        None => return Err(syntax_error(start, "unterminated comment")),
+                    }
+                }
+            }
+            '{' => tokens.push((Token::OpenBrace, line)),
+            '}' => tokens.push((Token::CloseBrace, line)),
+            '[' => tokens.push((Token::OpenBracket, line)),
+            ']' => tokens.push((Token::CloseBracket, line)),
+            '=' => tokens.push((Token::Equals, line)),
+            ';' => tokens.push((Token::Semicolon, line)),
+            ',' => tokens.push((Token::Comma, line)),
+            ':' => tokens.push((Token::Colon, line)),
+            '-' if chars.peek() == Some(&'>') => {
+                chars.next();
+                tokens.push((Token::Edge { directed: true }, line));
+            }
+            '-' if chars.peek() == Some(&'-') => {
+                chars.next();
+                tokens.push((Token::Edge { directed: false }, line));
+            }
+            '"' => {
+                let start = line;
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') if matches!(chars.peek(), Some('"' | '\\')) => value.push(chars.next().unwrap()),
+                        Some('\\') if chars.peek() == Some(&'\n') => {
+                            chars.next();
+                            line += 1;
+                        }
+                        Some(next) => {
+                            if next == '\n' {
+                                line += 1;
+                            }
+                            value.push(next);
+                        }
+                        None => return Err(syntax_error(start, "unterminated string")),
+                    }
+                }
+                tokens.push((Token::Id(value), start));
+            }
+            '<' => return Err(syntax_error(line, "HTML strings are not supported")),
+            _ if char.is_alphanumeric() || char == '_' || char == '-' || char == '.' => {
+                let mut value = String::from(char);
+                while let Some(next) = chars.next_if(|&next| next.is_alphanumeric() || next == '_' || next == '.') {
+                    value.push(next);
+                }
+                tokens.push((Token::Id(value), line));
+            }
+            _ => return Err(syntax_error(line, format!("unexpected character '{}'", char))),
+        }
+
+        at_line_start = false;
+    }
+
+    return Ok(tokens);
+}
+
+struct DotParser {
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+}
+
+impl DotParser {
+    fn peek(&self) -> Option<&Token> {
+        return self.tokens.get(self.position).map(|(token, _)| token);
+    }
+
+    fn line(&self) -> usize {
+        return self.tokens.get(self.position).or(self.tokens.last()).map_or(1, |&(_, line)| line);
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).map(|(token, _)| token.clone());
+        self.position += 1;
+
+        return token;
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), ParseError> {
+        let line = self.line();
+
+        match self.next() {
+            Some(token) if token == expected => return Ok(()),
+            Some(token) => return Err(syntax_error(line, format!("expected {:?}, found {:?}", expected, token))),
+            None => return Err(syntax_error(line, format!("expected {:?}, found end of input", expected))),
+        }
+    }
+
+    fn expect_id(&mut self) -> Result<String, ParseError> {
+        let line = self.line();
+
+        match self.next() {
+            Some(Token::Id(id)) => return Ok(id),
+            Some(token) => return Err(syntax_error(line, format!("expected identifier, found {:?}", token))),
+            None => return Err(syntax_error(line, "expected identifier, found end of input")),
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        return matches!(self.peek(), Some(Token::Id(id)) if id.eq_ignore_ascii_case(keyword));
+    }
+
+    fn parse_attributes(&mut self) -> Result<HashMap<String, String>, ParseError> {
+        let mut attributes: HashMap<String, String> = HashMap::new();
+
+        while self.peek() == Some(&Token::OpenBracket) {
+            self.next();
+
+            while self.peek() != Some(&Token::CloseBracket) {
+                let name = self.expect_id()?;
+                self.expect(Token::Equals)?;
+                let value = self.expect_id()?;
+                attributes.insert(name, value);
+
+                if matches!(self.peek(), Some(Token::Comma | Token::Semicolon)) {
+                    self.next();
+                }
+            }
+
+            self.expect(Token::CloseBracket)?;
+        }
+
+        return Ok(attributes);
+    }
+
+    fn parse_node<N: Copy + Eq + Hash + FromStr, E: Copy + PartialEq>(&mut self, graph: &mut Graph<N, E>) -> Result<N, ParseError> {
+        let line = self.line();
+        let id = self.expect_id()?;
+
+        if self.peek() == Some(&Token::Colon) {
+            return Err(syntax_error(self.line(), "ports are not supported"));
+        }
+
+        let node: N = id.parse().map_err(|_| ParseError::Invalid(format!("invalid node id on line {}: {}", line, id)))?;
+
+        if !graph.has_node(node) {
+            graph.add_node(node);
+        }
+
+        return Ok(node);
+    }
+
+    fn parse<N, E>(&mut self, default_weight: E) -> Result<Graph<N, E>, ParseError>
+    where
+        N: Copy + Eq + Hash + FromStr,
+        E: Copy + PartialEq + FromStr,
+    {
+        let mut graph: Graph<N, E> = Graph::default();
+        let mut edge_defaults: HashMap<String, String> = HashMap::new();
+
+        if self.is_keyword("strict") {
+            self.next();
+        }
+
+        let is_directed = if self.is_keyword("digraph") {
+            true
+        } else if self.is_keyword("graph") {
+            false
+        } else {
+            return Err(syntax_error(self.line(), "expected graph or digraph"));
+        };
+        self.next();
+
+        if matches!(self.peek(), Some(Token::Id(_))) {
+            self.next();
+        }
+
+        self.expect(Token::OpenBrace)?;
+
+        loop {
+            let line = self.line();
+
+            match self.peek() {
+                Some(Token::CloseBrace) => {
+                    self.next();
+                    break;
+                }
+                Some(Token::Semicolon) => {
+                    self.next();
+                }
+                Some(Token::OpenBrace) => return Err(syntax_error(line, "subgraphs are not supported")),
+                None => return Err(syntax_error(line, "expected }, found end of input")),
+                _ if self.is_keyword("subgraph") => return Err(syntax_error(line, "subgraphs are not supported")),
+                _ if self.is_keyword("edge") => {
+                    self.next();
+                    edge_defaults.extend(self.parse_attributes()?);
+                }
+                _ if self.is_keyword("graph") || self.is_keyword("node") => {
+                    self.next();
+                    self.parse_attributes()?;
+                }
+                _ if self.tokens.get(self.position + 1).map(|(token, _)| token) == Some(&Token::Equals) => {
+                    self.next();
+                    self.next();
+                    self.expect_id()?;
+                }
+                _ => {
+                    let mut chain: Vec<N> = vec![self.parse_node(&mut graph)?];
+
+                    while let Some(&Token::Edge { directed }) = self.peek() {
+                        if directed != is_directed {
+                            return Err(syntax_error(self.line(), if is_directed { "expected -> in digraph" } else { "expected -- in graph" }));
+                        }
+
+                        self.next();
+                        chain.push(self.parse_node(&mut graph)?);
+                    }
+
+                    let mut attributes = edge_defaults.clone();
+                    attributes.extend(self.parse_attributes()?);
+
+                    if chain.len() > 1 {
+                        let weight: E = match attributes.get("weight").or(attributes.get("label")) {
+                            Some(weight) => weight.parse().map_err(|_| ParseError::Invalid(format!("invalid edge weight on line {}: {}", line, weight)))?,
+                            None => default_weight,
+                        };
+                        let is_both = attributes.get("dir").is_some_and(|dir| dir == "both");
+
+                        for pair in chain.windows(2) {
+                            if is_directed && !is_both {
+                                graph.add_edge_directed(pair[0], pair[1], weight);
+                            } else {
+                                graph.add_edge(pair[0], pair[1], weight);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some((token, line)) = self.tokens.get(self.position) {
+            return Err(syntax_error(*line, format!("unexpected {:?} after end of graph", token)));
+        }
+
+        return Ok(graph);
+    }
+}
@@ -0,0 +1,287 @@
+use crate::{Graph, ParseError};
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::io;
+use std::io::{BufRead, Write};
+use std::str::FromStr;
+
+/// Options for reading and writing edge lists and other line-based formats (DIMACS, Matrix Market, adjacency matrices).
+#[derive(Clone, Debug)]
+pub struct EdgeListOptions<E> {
+    /// If `true` (default), every line is a one-way edge added with `add_edge_directed()`, otherwise edges are added with `add_edge()`.
+    /// When writing an undirected edge list, edges existing in both directions are written once.
+    pub directed: bool,
+    /// Lines starting with one of these prefixes are skipped. `#` and `%` by default.
+    pub comment_prefixes: Vec<String>,
+    /// Weight of edges which have no weight column. If `None` (default), such edges are reported as errors.
+    pub default_weight: Option<E>,
+}
+
+impl<E> Default for EdgeListOptions<E> {
+    fn default() -> Self {
+        Self {
+            directed: true,
+            comment_prefixes: vec![String::from("#"), String::from("%")],
+            default_weight: None,
+        }
+    }
+}
+
+impl<E> EdgeListOptions<E> {
+    pub(crate) fn is_comment(&self, line: &str) -> bool {
+        return self.comment_prefixes.iter().any(|prefix| line.starts_with(prefix.as_str()));
+    }
+}
+
+pub(crate) fn parse_token<T: FromStr>(token: &str, line: usize, what: &str) -> Result<T, ParseError> {
+    return token.parse().map_err(|_| ParseError::Syntax { line, message: format!("invalid {}: {}", what, token) });
+}
+
+pub(crate) fn invalid_input(message: String) -> io::Error {
+    return io::Error::new(io::ErrorKind::InvalidInput, message);
+}
+
+impl<N: Copy + Eq + Hash, E: Copy + PartialEq> Graph<N, E> {
+    /// Reads a graph from a whitespace-separated edge list, one `source target [weight]` edge per line (e.g. SNAP datasets).
+    /// A line with a single node adds that node without edges. Nodes which appear only in edges are added to the graph.
+    pub fn from_edge_list(edge_list: &str, options: &EdgeListOptions<E>) -> Result<Self, ParseError>
+    where
+        N: FromStr,
+        E: FromStr,
+    {
+        return Self::read_edge_list(edge_list.as_bytes(), options);
+    }
+
+    /// Reads a graph from a whitespace-separated edge list, reading it from `reader`. See `from_edge_list()`.
+    pub fn read_edge_list<R: BufRead>(reader: R, options: &EdgeListOptions<E>) -> Result<Self, ParseError>
+    where
+        N: FromStr,
+        E: FromStr,
+    {
+        let mut graph = Self::default();
+
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line_number = index + 1;
+            let line = line.trim();
+
+            if line.is_empty() || options.is_comment(line) {
+                continue;
+            }
+
+            let columns: Vec<&str> = line.split_whitespace().collect();
+
+            match columns[..] {
+                [node] => {
+                    let node: N = parse_token(node, line_number, "node")?;
+
+                    if !graph.has_node(node) {
+                        graph.add_node(node);
+                    }
+                }
+                [source, target] | [source, target, _] => {
+                    let weight = match columns.get(2) {
+                        Some(weight) => parse_token(weight, line_number, "edge weight")?,
+                        None => options.default_weight.ok_or_else(|| ParseError::Syntax {
+                            line: line_number,
+                            message: String::from("edge has no weight and no default weight is set"),
+                        })?,
+                    };
+
+                    graph.insert_edge(parse_token(source, line_number, "node")?, parse_token(target, line_number, "node")?, weight, options.directed);
+                }
+                _ => {
+                    return Err(ParseError::Syntax {
+                        line: line_number,
+                        message: format!("expected source, target and weight, found {} columns", columns.len()),
+                    });
+                }
+            }
+        }
+
+        return Ok(graph);
+    }
+
+    /// Writes a graph as a whitespace-separated edge list, one `source target weight` edge per line.
+    /// Nodes without edges are written on their own lines.
+    pub fn to_edge_list(&self, options: &EdgeListOptions<E>) -> String
+    where
+        N: Display,
+        E: Display,
+    {
+        let mut buffer: Vec<u8> = Vec::new();
+        self.write_edge_list(&mut buffer, options).unwrap();
+
+        return String::from_utf8(buffer).unwrap();
+    }
+
+    /// Writes a graph as a whitespace-separated edge list to `writer`. Produces the same output as `to_edge_list()`.
+    pub fn write_edge_list<W: Write>(&self, mut writer: W, options: &EdgeListOptions<E>) -> io::Result<()>
+    where
+        N: Display,
+        E: Display,
+    {
+        let targets: HashSet<N> = self.nodes.values().flatten().map(|&(target, _)| target).collect();
+
+        for (node, adjacents) in &self.nodes {
+            if adjacents.is_empty() && !targets.contains(node) {
+                writeln!(writer, "{}", node)?;
+            }
+        }
+
+        if options.directed {
+            for (source, adjacents) in &self.nodes {
+                for (target, weight) in adjacents {
+                    writeln!(writer, "{}\t{}\t{}", source, target, weight)?;
+                }
+            }
+        } else {
+            for (source, target, weight, _) in self.edges_by_direction() {
+                writeln!(writer, "{}\t{}\t{}", source, target, weight)?;
+            }
+        }
+
+        return Ok(());
+    }
+
+    /// Reads a graph from the DIMACS shortest path format (`.gr` files): a `p sp <nodes> <arcs>` problem line followed by `a <source> <target> <weight>` arcs.
+    /// DIMACS nodes are numbered from 1, node `i` is added to the graph as `i - 1`. Lines starting with `c` are comments.
+    pub fn from_dimacs(dimacs: &str, options: &EdgeListOptions<E>) -> Result<Self, ParseError>
+    where
+        N: TryFrom<usize>,
+        E: FromStr,
+    {
+        return Self::read_dimacs(dimacs.as_bytes(), options);
+    }
+
+    /// Reads a graph from the DIMACS shortest path format, reading it from `reader`. See `from_dimacs()`.
+    pub fn read_dimacs<R: BufRead>(reader: R, options: &EdgeListOptions<E>) -> Result<Self, ParseError>
+    where
+        N: TryFrom<usize>,
+        E: FromStr,
+    {
+        let mut graph = Self::default();
+        let mut node_count: Option<usize> = None;
+
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line_number = index + 1;
+            let columns: Vec<&str> = line.split_whitespace().collect();
+
+            match columns[..] {
+                [] | ["c", ..] => {}
+                ["p", _, nodes, _] if node_count.is_none() => {
+                    let nodes: usize = parse_token(nodes, line_number, "node count")?;
+
+                    for index in 0..nodes {
+                        graph.add_node(index_to_node(index, line_number)?);
+                    }
+
+                    node_count = Some(nodes);
+                }
+                ["a", source, target, ..] if columns.len() <= 4 => {
+                    let Some(nodes) = node_count else {
+                        return Err(ParseError::Syntax { line: line_number, message: String::from("arc before the problem line") });
+                    };
+
+                    let source: usize = parse_token(source, line_number, "node")?;
+                    let target: usize = parse_token(target, line_number, "node")?;
+
+                    if !(1..=nodes).contains(&source) || !(1..=nodes).contains(&target) {
+                        return Err(ParseError::Syntax { line: line_number, message: format!("arc {} {} is out of node range 1..{}", source, target, nodes) });
+                    }
+
+                    let weight = match columns.get(3) {
+                        Some(weight) => parse_token(weight, line_number, "arc weight")?,
+                        None => options.default_weight.ok_or_else(|| ParseError::Syntax {
+                            line: line_number,
+                            message: String::from("arc has no weight and no default weight is set"),
+                        })?,
+                    };
+
+                    graph.insert_edge(index_to_node(source - 1, line_number)?, index_to_node(target - 1, line_number)?, weight, options.directed);
+                }
+                _ => return Err(ParseError::Syntax { line: line_number, message: format!("unexpected line: {}", line) }),
+            }
+        }
+
+        if node_count.is_none() {
+            return Err(ParseError::Syntax { line: 1, message: String::from("missing problem line") });
+        }
+
+        return Ok(graph);
+    }
+
+    /// Writes a graph in the DIMACS shortest path format. Node `i` is written as `i + 1`, so nodes have to be numbered from 0.
+    pub fn to_dimacs(&self) -> io::Result<String>
+    where
+        usize: TryFrom<N>,
+        E: Display,
+    {
+        let mut buffer: Vec<u8> = Vec::new();
+        self.write_dimacs(&mut buffer)?;
+
+        return Ok(String::from_utf8(buffer).unwrap());
+    }
+
+    /// Writes a graph in the DIMACS shortest path format to `writer`. See `to_dimacs()`.
+    pub fn write_dimacs<W: Write>(&self, mut writer: W) -> io::Result<()>
+    where
+        usize: TryFrom<N>,
+        E: Display,
+    {
+        let node_count = self.node_index_bound()?;
+        let arc_count: usize = self.nodes.values().map(|adjacents| adjacents.len()).sum();
+
+        writeln!(writer, "p sp {} {}", node_count, arc_count)?;
+
+        for (&source, adjacents) in &self.nodes {
+            for &(target, weight) in adjacents {
+                writeln!(writer, "a {} {} {}", node_to_index(source)? + 1, node_to_index(target)? + 1, weight)?;
+            }
+        }
+
+        return Ok(());
+    }
+
+    /// Adds an edge, adding `source` and `target` to the graph if they are not present yet.
+    pub(crate) fn insert_edge(&mut self, source: N, target: N, weight: E, directed: bool) {
+        for node in [source, target] {
+            if !self.has_node(node) {
+                self.add_node(node);
+            }
+        }
+
+        if directed {
+            self.add_edge_directed(source, target, weight);
+        } else {
+            self.add_edge(source, target, weight);
+        }
+    }
+
+    /// Returns one more than the largest node index, checking that every node can be used as an index.
+    pub(crate) fn node_index_bound(&self) -> io::Result<usize>
+    where
+        usize: TryFrom<N>,
+    {
+        let mut bound = 0;
+
+        for &node in self.nodes.keys() {
+            bound = bound.max(node_to_index(node)? + 1);
+        }
+
+        return Ok(bound);
+    }
+}
+
+pub(crate) fn index_to_node<N: TryFrom<usize>>(index: usize, line: usize) -> Result<N, ParseError> {
+    return N::try_from(index).map_err(|_| ParseError::Syntax { line, message: format!("node {} doesn't fit into the node type", index) });
+}
+
+pub(crate) fn node_to_index<N>(node: N) -> io::Result<usize>
+where
+    usize: TryFrom<N>,
+{
+    return usize::try_from(node).map_err(|_| invalid_input(String::from("nodes have to be non-negative integers to be written as indices")));
+}
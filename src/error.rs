@@ -0,0 +1,114 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// Errors which can occur while reading a graph from its serialized form.
+/// Variants depend on enabled cargo features (`Json` needs `json`, `Xml` needs `graphml`), so matches need a wildcard arm.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ParseError {
+    /// Input could not be read.
+    Io(io::Error),
+    /// Input is not valid JSON or doesn't match the layout produced by `Graph::serialize()`.
+    #[cfg(feature = "json")]
+    Json(serde_json::Error),
+    /// Input is not well-formed XML.
+    #[cfg(feature = "graphml")]
+    Xml(quick_xml::Error),
+    /// Input doesn't follow the syntax of a text format, e.g. DOT.
+    Syntax {
+        line: usize,
+        message: String,
+    },
+    /// Input is well-formed, but doesn't describe a valid graph, e.g. a node id or an edge weight can't be parsed.
+    Invalid(String),
+    /// An edge points to a node which is not present in the graph.
+    DanglingEdge {
+        source: String,
+        target: String,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Io(error) => write!(f, "failed to read input: {}", error),
+            #[cfg(feature = "json")]
+            ParseError::Json(error) => write!(f, "invalid JSON: {}", error),
+            #[cfg(feature = "graphml")]
+            ParseError::Xml(error) => write!(f, "invalid XML: {}", error),
+            ParseError::Syntax { line, message } => write!(f, "syntax error on line {}: {}", line, message),
+            ParseError::Invalid(message) => write!(f, "invalid graph: {}", message),
+            ParseError::DanglingEdge { source, target } => {
+                write!(f, "edge from {} points to unknown node {}", source, target)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseError::Io(error) => Some(error),
+            #[cfg(feature = "json")]
+            ParseError::Json(error) => Some(error),
+            #[cfg(feature = "graphml")]
+            ParseError::Xml(error) => Some(error),
+            ParseError::Syntax { .. } | ParseError::Invalid(_) | ParseError::DanglingEdge { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for ParseError {
+    fn from(error: io::Error) -> Self {
+        ParseError::Io(error)
+    }
+}
+
+#[cfg(feature = "json")]
+impl From<serde_json::Error> for ParseError {
+    fn from(error: serde_json::Error) -> Self {
+        ParseError::Json(error)
+    }
+}
+
+#[cfg(feature = "graphml")]
+impl From<quick_xml::Error> for ParseError {
+    fn from(error: quick_xml::Error) -> Self {
+        ParseError::Xml(error)
+    }
+}
+
+/// Errors which can occur while working with a graph, returned by `try_*` functions of `Graph`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GraphError<N = u32> {
+    /// Node is not present in the graph.
+    UnknownNode(N),
+    /// There is no path from `source` to `target`.
+    NoPath {
+        source: N,
+        target: N,
+    },
+    /// Graph contains a cycle of negative weight reachable from the source, so shortest paths are not defined.
+    /// Holds nodes of the cycle in order, the last node has an edge to the first one.
+    NegativeCycle(Vec<N>),
+    /// Graph contains a cycle, but the operation requires a directed acyclic graph.
+    /// Holds nodes of the cycle in order, the last node has an edge to the first one.
+    Cycle(Vec<N>),
+    /// Arguments are not valid for the operation, e.g. an edge from a node to itself.
+    InvalidInput(String),
+}
+
+impl<N: fmt::Debug> fmt::Display for GraphError<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphError::UnknownNode(node) => write!(f, "node {:?} is not present in the graph", node),
+            GraphError::NoPath { source, target } => write!(f, "there is no path from {:?} to {:?}", source, target),
+            GraphError::NegativeCycle(cycle) => write!(f, "graph contains a negative cycle {:?}", cycle),
+            GraphError::Cycle(cycle) => write!(f, "graph contains a cycle {:?}", cycle),
+            GraphError::InvalidInput(message) => write!(f, "invalid input: {}", message),
+        }
+    }
+}
+
+impl<N: fmt::Debug> Error for GraphError<N> {}
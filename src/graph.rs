@@ -1,145 +1,426 @@
 //! ## Mgraph
 //! mgraph is a simple, fast, performace-oriented graph library for Rust. It's being contributed to on daily basis and grows fast!
-//! 
+//!
 //! ## Features
-//! 
+//!
 //! Features that are implemented already:
-//! 
-//! -  DFS algorithm
-//! -  BFS algorithm
-//! -  Dijkstra shortest path algorithm
-//! -  Serialization and deserialization of graphs
-//! 
+//!
+//! -  DFS algorithm, also as a lazy iterator with pre-order, post-order and edge classification events
+//! -  BFS algorithm, with hop distances, unweighted shortest paths and hop limits
+//! -  Connected components, with component labels and the largest component
+//! -  Strongly connected components (Tarjan algorithm) and condensation of directed graphs
+//! -  Topological sort (Kahn and DFS-based), longest and critical paths and transitive reduction of DAGs
+//! -  Cycle detection for directed and undirected graphs and enumeration of elementary cycles (Johnson algorithm)
+//! -  Filtered views of a graph, for searches which avoid certain nodes or edges
+//! -  Dijkstra shortest path algorithm, for a pair of nodes or from one node to every other
+//! -  K shortest loopless paths (Yen algorithm)
+//! -  Bidirectional Dijkstra algorithm for point-to-point queries
+//! -  Contraction Hierarchies index for fast shortest path queries on static graphs
+//! -  A* algorithm with heuristics based on node coordinates
+//! -  Optional 2D, 3D or geographic coordinates of nodes
+//! -  Grid graphs built from 2D cost maps
+//! -  Bellman-Ford algorithm and its queue-based variant (SPFA) for negative weights, with negative cycle detection
+//! -  All-pairs shortest paths (Floyd-Warshall and Johnson algorithms)
+//! -  Serialization and deserialization of graphs (JSON, or any serde format with the `serde` feature)
+//! -  GraphML import and export
+//! -  Graphviz DOT import and export
+//! -  Edge lists, DIMACS, Matrix Market and adjacency matrix text formats
+//! -  Generic node and edge types, including floating point weights and node payloads (see below)
+//!
 //! Features that are to be implemented in future:
-//! 
-//! -  A* algorithm
+//!
 //! -  Other intresting things of graph theory, such as different search algorithms, sorting algorithms etc.
 //!
 //! ## Example usage
-//! 
+//!
 //! ```
 //! let mut graph = mgraph::Graph::new();
-//! 
+//!
 //! graph.add_node(0);
 //! graph.add_node(1);
 //! graph.add_node(2);
 //! graph.add_node(3);
-//! 
+//!
 //! graph.add_edge(0, 1, 6);
 //! graph.add_edge(0, 2, 16);
 //! graph.add_edge(1, 2, 7);
 //! graph.add_edge(2, 3, 8);
-//! 
+//!
 //! let result = graph.shortest_path(0, 2);
-//! 
-//! let parents = result.parents.unwrap();
-//! let cost = result.cost.unwrap();
-//! 
-//! println!("{:#?}\n\n{:?}", cost, parents);
-//! 
-//! let shortest_path = graph.restore_path(0, 2, parents);
-//! ``` 
+//!
+//! let path = result.path.unwrap();
+//!
+//! for (source, target, weight) in path.edges() {
+//!     println!("{} -> {}: {}", source, target, weight);
+//! }
+//!
+//! assert_eq!(path.nodes, vec![0, 1, 2]);
+//! assert_eq!(path.cost, 13);
+//! ```
+//!
+//! ## Cargo features
+//!
+//! -  `json` (enabled by default): `Graph::serialize()` and `Graph::deserialize()` using `serde_json`.
+//! -  `graphml` (enabled by default): GraphML import and export using `quick-xml`.
+//! -  `serde`: `serde::Serialize` and `serde::Deserialize` implementations for `Graph`, `DijkstraResult` and `ContractionHierarchy`.
+//!    Enabled by `json`, can be used on its own with `default-features = false` to store graphs in any serde format.
+//!
+//! ## Generic graphs
+//!
+//! `Graph::new()` creates a graph with `u32` nodes and `i32` weights. Any `Copy + Eq + Hash` type can be used as a node,
+//! and any `Copy + PartialEq` type can be used as an edge payload. Path-finding algorithms additionally require the edge
+//! payload to implement [`Weight`], which all primitive integers and floats do.
+//!
+//! ```
+//! let mut graph: mgraph::Graph<&str, f64> = mgraph::Graph::default();
+//!
+//! graph.add_node("Kyiv");
+//! graph.add_node("Lviv");
+//! graph.add_edge("Kyiv", "Lviv", 540.5);
+//!
+//! assert_eq!(graph.shortest_path("Kyiv", "Lviv").cost, Some(540.5));
+//! ```
+//!
+//! Nodes which own their data (e.g. `String`) can be given `usize` ids with [`NodeIds`] and added to a `Graph<usize, E>`:
+//!
+//! ```
+//! let mut cities: mgraph::NodeIds<String> = mgraph::NodeIds::new();
+//! let mut graph: mgraph::Graph<usize, f64> = mgraph::Graph::default();
+//!
+//! let kyiv = cities.insert(String::from("Kyiv"));
+//! let lviv = cities.insert(String::from("Lviv"));
+//!
+//! graph.add_node(kyiv);
+//! graph.add_node(lviv);
+//! graph.add_edge(kyiv, lviv, 540.5);
+//!
+//! let path = graph.shortest_path(kyiv, lviv).path.unwrap();
+//! let names: Vec<&String> = path.nodes.iter().filter_map(|&node| cities.get(node)).collect();
+//!
+//! assert_eq!(names, vec!["Kyiv", "Lviv"]);
+//! ```
 
+#![allow(clippy::needless_return)]
+
+mod all_pairs;
+mod bidirectional;
+mod components;
+mod contraction;
+mod cycles;
+mod dag;
+mod dot;
+mod edge_list;
+mod error;
+#[cfg(feature = "graphml")]
+mod graphml;
+mod grid;
+#[cfg(feature = "json")]
+mod json;
+mod k_shortest;
+mod matrix;
+mod node_ids;
+mod path;
+#[cfg(feature = "serde")]
+mod serde_impls;
+mod spatial;
+mod traversal;
+mod view;
+
+pub use all_pairs::AllPairsShortestPaths;
+pub use components::Components;
+pub use contraction::ContractionHierarchy;
+pub use edge_list::EdgeListOptions;
+pub use error::{GraphError, ParseError};
+pub use grid::{Connectivity, GridOptions};
+pub use node_ids::NodeIds;
+pub use path::{Path, ShortestPathTree};
+pub use spatial::{Coordinates, Heuristic};
+pub use traversal::{Bfs, BfsResult, Dfs, DfsEvent};
+pub use view::GraphView;
+
+use path::path_from_parents;
 
-use serde_json;
 use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::collections::BinaryHeap;
+use std::hash::Hash;
+use std::ops::{Add, Sub};
 
 /// Graph data structure
-#[derive(Debug)]
-pub struct Graph {
-    /// Nodes data structure, a HashMap of a node and a tuple of adjacent node and edge weight. 
-    /// Nodes and weights are represented as integers of type `u32`.
+#[derive(Debug, Clone)]
+pub struct Graph<N = u32, E = i32> {
+    /// Nodes data structure, a HashMap of a node and a tuple of adjacent node and edge weight.
+    /// By default nodes are represented as integers of type `u32` and weights as integers of type `i32`.
     /// This implementation of graph data structure uses adjacentcy list architecture rather than adjacency matrix because of second's bad performance
-    pub nodes: HashMap<u32, HashSet<(u32, i32)>>,
+    /// Every `(adjacent node, weight)` pair of a node is stored once, in the order the edges were added.
+    pub nodes: HashMap<N, Vec<(N, E)>>,
+    /// Optional coordinates of nodes, used by heuristics of A* algorithm. Nodes without coordinates are not present in the map.
+    pub coordinates: HashMap<N, Coordinates>,
+}
+
+/// Edge weight which can be used by path-finding algorithms.
+/// Implemented for all primitive integer and floating point types. Weights which can't be compared (`NaN`) are treated as equal to any other weight.
+pub trait Weight: Copy + PartialOrd + Add<Output = Self> + Sub<Output = Self> {
+    /// Weight of an empty path.
+    fn zero() -> Self;
+    /// Converts a geometric distance into a weight, rounding it down so that heuristics built on it stay admissible.
+    /// Negative distances become zero and distances which don't fit into the type saturate at its maximum value.
+    fn from_distance(distance: f64) -> Self;
+    /// Converts a weight into a geometric distance.
+    fn to_distance(self) -> f64;
+    /// Adds two weights, returning `None` if the sum doesn't fit into the type. Paths whose cost would overflow are treated as unreachable.
+    fn checked_add(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_weight {
+    ($($type:ty),*) => {
+        $(
+            impl Weight for $type {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn from_distance(distance: f64) -> Self {
+                    distance.max(0.0) as $type
+                }
+
+                fn to_distance(self) -> f64 {
+                    self as f64
+                }
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$type>::checked_add(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl_weight!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_float_weight {
+    ($($type:ty),*) => {
+        $(
+            impl Weight for $type {
+                fn zero() -> Self {
+                    0.0
+                }
+
+                fn from_distance(distance: f64) -> Self {
+                    distance.max(0.0) as $type
+                }
+
+                fn to_distance(self) -> f64 {
+                    self as f64
+                }
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    Some(self + other)
+                }
+            }
+        )*
+    };
+}
+
+impl_float_weight!(f32, f64);
+
+/// Compares two weights, treating weights which can't be compared as equal.
+pub(crate) fn compare_weights<E: Weight>(a: &E, b: &E) -> Ordering {
+    return a.partial_cmp(b).unwrap_or(Ordering::Equal);
 }
 
-#[derive(Copy, Clone, Eq, PartialEq)]
-struct DijkstraState {
-    node: u32,
-    cost: i32,
+#[derive(Copy, Clone)]
+struct DijkstraState<N, E> {
+    node: N,
+    cost: E,
 }
 
 /// DijkstraResult structure contains shortest path algorithm return values
-#[derive(PartialEq, Debug)]
-pub struct DijkstraResult {
-    /// Cost of shortest path. `Option<u32>` if exists, `None` if no path was found
-    pub cost: Option<u32>, 
-    /// Map of a node and its predecessor, used in `resore_path()`. `Option<HashMap<u32, i32>>` if exists, `None` otherwise.
-    pub parents: Option<HashMap<u32, i32>>
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(
+    serialize = "N: serde::Serialize, E: serde::Serialize",
+    deserialize = "N: serde::Deserialize<'de> + Eq + Hash, E: serde::Deserialize<'de>"
+)))]
+pub struct DijkstraResult<N = u32, E = i32> {
+    /// Cost of shortest path. `Some(cost)` if exists, `None` if no path was found
+    pub cost: Option<E>,
+    /// Map of a node and its predecessor, used in `resore_path()`. `Some(parents)` if exists, `None` otherwise.
+    pub parents: Option<HashMap<N, N>>,
+    /// The shortest path itself. `Some(path)` if exists, `None` otherwise.
+    pub path: Option<Path<N, E>>,
+}
+
+/// Result of Bellman-Ford algorithm, returned by `Graph::bellman_ford()`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(
+    serialize = "N: serde::Serialize, E: serde::Serialize",
+    deserialize = "N: serde::Deserialize<'de> + Eq + Hash, E: serde::Deserialize<'de>"
+)))]
+pub struct BellmanFordResult<N = u32, E = i32> {
+    /// Node the paths start from.
+    pub source: N,
+    /// Map of every reachable node and the cost of the shortest path to it.
+    pub distances: HashMap<N, E>,
+    /// Map of every reachable node except `source` and its predecessor on the shortest path, together with the weight of the edge between them.
+    pub parents: HashMap<N, (N, E)>,
+    /// Nodes of a cycle of negative weight reachable from `source` in order, the last node has an edge to the first one. `None` if there is no such cycle.
+    pub negative_cycle: Option<Vec<N>>,
 }
 
-impl Ord for DijkstraState {
+impl<N: Copy + Eq + Hash, E: Weight> BellmanFordResult<N, E> {
+    /// Returns the cost of the shortest path to `target`, or `None` if it can't be reached or if there is a negative cycle.
+    pub fn distance(&self, target: N) -> Option<E> {
+        if self.negative_cycle.is_some() {
+            return None;
+        }
+
+        return self.distances.get(&target).copied();
+    }
+
+    /// Returns the shortest path from `source` to `target`, or `None` if it can't be reached or if there is a negative cycle.
+    pub fn path_to(&self, target: N) -> Option<Path<N, E>> {
+        if self.negative_cycle.is_some() {
+            return None;
+        }
+
+        return path_from_parents(self.source, target, &self.parents);
+    }
+
+    /// Converts the result into a `ShortestPathTree`, or returns `GraphError::NegativeCycle` if there is a negative cycle.
+    pub fn into_tree(self) -> Result<ShortestPathTree<N, E>, GraphError<N>> {
+        if let Some(cycle) = self.negative_cycle {
+            return Err(GraphError::NegativeCycle(cycle));
+        }
+
+        return Ok(ShortestPathTree { source: self.source, distances: self.distances, parents: self.parents });
+    }
+}
+
+impl<N: Eq + Hash, E: PartialEq> PartialEq for DijkstraResult<N, E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cost == other.cost && self.parents == other.parents && self.path == other.path
+    }
+}
+
+impl<N, E: Weight> Ord for DijkstraState<N, E> {
     fn cmp(&self, other: &Self) -> Ordering {
-        other.cost.cmp(&self.cost).then_with(|| self.node.cmp(&other.node))
+        compare_weights(&other.cost, &self.cost)
     }
 }
 
-impl PartialOrd for DijkstraState {
+impl<N, E: Weight> PartialOrd for DijkstraState<N, E> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl Graph {
+impl<N, E: Weight> PartialEq for DijkstraState<N, E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
 
-    /// Creates new graph of the `Graph` type.
+impl<N, E: Weight> Eq for DijkstraState<N, E> {}
+
+impl Graph {
+    /// Creates new graph of the `Graph` type with `u32` nodes and `i32` weights.
+    /// Use `Graph::default()` to create a graph with other node and edge types.
     pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<N, E> Default for Graph<N, E> {
+    fn default() -> Self {
         Self {
             nodes: HashMap::new(),
+            coordinates: HashMap::new(),
         }
     }
+}
 
-    /// Adds a node to the graph.
+impl<N, E> Graph<N, E> {
+    pub fn manhattan_distance(&self, source: (u32, u32), target: (u32, u32)) -> u32 {
+        return source.0.abs_diff(target.0) + source.1.abs_diff(target.1)
+    }
 
-    pub fn add_node(&mut self, node: u32) {
-        self.nodes.insert(node, HashSet::new());
+    pub fn euclidian_distance(&self, source: (u32, u32), target: (u32, u32)) -> f32 {
+        return (source.0.abs_diff(target.0).pow(2) as f32 + source.1.abs_diff(target.1).pow(2) as f32).sqrt()
     }
+}
 
-    /// Adds a directed edge between `source` and `target` with the weight `weight`. After using this function, an edge will appear for `source`, but not for `target`.
+impl<N: Copy + Eq + Hash, E: Copy + PartialEq> Graph<N, E> {
+    /// Adds a node to the graph.
+    pub fn add_node(&mut self, node: N) {
+        self.nodes.insert(node, Vec::new());
+    }
 
-    pub fn add_edge_directed(&mut self, source: u32, target: u32, weight: i32) {
+    /// Adds a directed edge between `source` and `target` with the weight `weight`. After using this function, an edge will appear for `source`, but not for `target`.
+    pub fn add_edge_directed(&mut self, source: N, target: N, weight: E) {
         if source != target {
             self.nodes
                 .entry(source)
-                .and_modify(|adjacency_set: &mut HashSet<(u32, i32)>| {
-                    adjacency_set.insert((target, weight));
+                .and_modify(|adjacents: &mut Vec<(N, E)>| {
+                    if !adjacents.contains(&(target, weight)) {
+                        adjacents.push((target, weight));
+                    }
                 });
         }
     }
 
     /// Adds an edge between `source` and `target` with the weight `weight`. After using this function, and edge will appear for both `source` and `target`.
-
-    pub fn add_edge(&mut self, source: u32, target: u32, weight: i32) {
+    pub fn add_edge(&mut self, source: N, target: N, weight: E) {
         self.add_edge_directed(source, target, weight);
         self.add_edge_directed(target, source, weight)
     }
 
-    /// Drops edge from `node_a` to `node_b`. After using this function, only edge from `node_a` to `node_b` will be terminated, however connection between `node_b` and `node_a` will still remain.
+    /// Adds a directed edge between `source` and `target` with the weight `weight`, like `add_edge_directed()`.
+    /// Returns `GraphError::UnknownNode` if `source` or `target` is not present in the graph and `GraphError::InvalidInput` if they are the same node.
+    pub fn try_add_edge_directed(&mut self, source: N, target: N, weight: E) -> Result<(), GraphError<N>> {
+        self.check_node(source)?;
+        self.check_node(target)?;
+
+        if source == target {
+            return Err(GraphError::InvalidInput(String::from("edge from a node to itself")));
+        }
+
+        self.add_edge_directed(source, target, weight);
+
+        return Ok(());
+    }
+
+    /// Adds an edge between `source` and `target` with the weight `weight`, like `add_edge()`. Fails in the same cases as `try_add_edge_directed()`.
+    pub fn try_add_edge(&mut self, source: N, target: N, weight: E) -> Result<(), GraphError<N>> {
+        self.try_add_edge_directed(source, target, weight)?;
+        self.add_edge_directed(target, source, weight);
 
-    pub fn drop_edge_directed(&mut self, node_a: u32, node_b: u32) {
+        return Ok(());
+    }
+
+    /// Drops edge from `node_a` to `node_b`. After using this function, only edge from `node_a` to `node_b` will be terminated, however connection between `node_b` and `node_a` will still remain.
+    pub fn drop_edge_directed(&mut self, node_a: N, node_b: N) {
         self.nodes
             .entry(node_a)
-            .and_modify(|adjacency_set: &mut HashSet<(u32, i32)>| {
-                adjacency_set.retain(|&(first, _)| first != node_b);
+            .and_modify(|adjacents: &mut Vec<(N, E)>| {
+                adjacents.retain(|&(first, _)| first != node_b);
 
             });
     }
 
     /// Drops edge from `node_a` to `node_b` and vise-versa. After using this function, no connection between nodes `node_a` and `node_b` will remain.
-
-    pub fn drop_edge(&mut self, node_a: u32, node_b: u32) {
+    pub fn drop_edge(&mut self, node_a: N, node_b: N) {
         self.drop_edge_directed(node_a, node_b);
         self.drop_edge_directed(node_b, node_a);
     }
 
-    /// Drops a node and all edges going to that node from adjacent nodes. 
-
-    pub fn drop_node(&mut self, node: u32) {
+    /// Drops a node and all edges going to that node from adjacent nodes.
+    pub fn drop_node(&mut self, node: N) {
         self.nodes.remove(&node);
+        self.coordinates.remove(&node);
         for (_, adjacents) in self.nodes.iter_mut() {
             adjacents.retain(|&adjacent| {
                 adjacent.0 != node
@@ -147,15 +428,13 @@ impl Graph {
         }
     }
 
-    /// Checks if graph has a certain node. 
-
-    pub fn has_node(&self, node: u32) -> bool {
+    /// Checks if graph has a certain node.
+    pub fn has_node(&self, node: N) -> bool {
         return self.nodes.contains_key(&node);
     }
 
     /// Checks if there is an edge from `node_a` to `node_b`.
-
-    pub fn has_edge_directed(&self, node_a: u32, node_b: u32) -> bool {
+    pub fn has_edge_directed(&self, node_a: N, node_b: N) -> bool {
         if self.has_node(node_a) && self.has_node(node_b) {
             return self.nodes[&node_a].iter().any(|&(first, _)| first == node_b);
         }
@@ -164,22 +443,41 @@ impl Graph {
     }
 
     /// Checks if there is and edge from `node_a` to `node_b` and an edge from `node_b` to `node_a`.
-
-    pub fn has_edge(&self, node_a: u32, node_b: u32) -> bool {
+    pub fn has_edge(&self, node_a: N, node_b: N) -> bool {
         return self.has_edge_directed(node_a, node_b) && self.has_edge_directed(node_b, node_a);
     }
 
     /// Returns all adjacent nodes of `node`
-
-    pub fn get_node_adjacents(&self, node: u32) -> &HashSet<(u32, i32)> {
+    ///
+    /// Panics if `node` is not present in the graph, see `try_get_node_adjacents()`.
+    pub fn get_node_adjacents(&self, node: N) -> &Vec<(N, E)> {
         return &self.nodes[&node];
     }
 
+    /// Returns all adjacent nodes of `node`, or `GraphError::UnknownNode` if `node` is not present in the graph.
+    pub fn try_get_node_adjacents(&self, node: N) -> Result<&Vec<(N, E)>, GraphError<N>> {
+        return self.nodes.get(&node).ok_or(GraphError::UnknownNode(node));
+    }
+
+    /// Returns `GraphError::UnknownNode` if `node` is not present in the graph.
+    pub(crate) fn check_node(&self, node: N) -> Result<(), GraphError<N>> {
+        if !self.has_node(node) {
+            return Err(GraphError::UnknownNode(node));
+        }
+
+        return Ok(());
+    }
+
+    /// Iterates over edges going out of `node`. Nodes which are not present in the graph have no edges,
+    /// which also covers targets of edges added with `add_edge_directed()` before the target node itself.
+    pub(crate) fn adjacents(&self, node: N) -> impl Iterator<Item = &(N, E)> {
+        return self.nodes.get(&node).into_iter().flatten();
+    }
+
     /// Returns all leaf nodes in graph.
     /// Leaf nodes are nodes which have only one adjacent node.
-
-    pub fn get_leaf_nodes(&self) -> HashSet<u32> {
-        let mut leaf_nodes: HashSet<u32> = HashSet::new();
+    pub fn get_leaf_nodes(&self) -> HashSet<N> {
+        let mut leaf_nodes: HashSet<N> = HashSet::new();
 
         for node in self.nodes.keys() {
             if self.get_node_adjacents(*node).len() == 1 {
@@ -190,17 +488,22 @@ impl Graph {
         return leaf_nodes;
     }
 
-    pub fn depth_first_search(&self, node: u32) -> HashSet<u32> {
-        let mut stack: VecDeque<u32> = VecDeque::new();
-        let mut visited_nodes: HashSet<u32> = HashSet::new();
+    pub fn depth_first_search(&self, node: N) -> HashSet<N> {
+        return self.depth_first_search_filtered(node, |_, _, _| true);
+    }
+
+    /// Runs `depth_first_search()` using only edges `(source, target, weight)` for which `is_allowed` returns `true`.
+    pub(crate) fn depth_first_search_filtered(&self, node: N, is_allowed: impl Fn(N, N, E) -> bool) -> HashSet<N> {
+        let mut stack: VecDeque<N> = VecDeque::new();
+        let mut visited_nodes: HashSet<N> = HashSet::new();
 
         stack.push_back(node);
 
         while let Some(node_pop) = stack.pop_back() {
-            if visited_nodes.contains(&node_pop) == false {
+            if !visited_nodes.contains(&node_pop) {
                 visited_nodes.insert(node_pop);
-                for adjacent in self.get_node_adjacents(node_pop).iter() {
-                    stack.push_back((*adjacent).0);
+                for adjacent in self.adjacents(node_pop).filter(|&&(adjacent, weight)| is_allowed(node_pop, adjacent, weight)) {
+                    stack.push_back(adjacent.0);
                 }
             }
         }
@@ -208,228 +511,453 @@ impl Graph {
         return visited_nodes;
     }
 
-    pub fn breadth_first_search(&self, node: u32, target: u32) -> i32 {
-        let mut queue: VecDeque<u32> = VecDeque::new();
-        let mut visited_nodes: HashSet<u32> = HashSet::new();
+    /// Runs `depth_first_search()`, returning `GraphError::UnknownNode` if `node` is not present in the graph.
+    pub fn try_depth_first_search(&self, node: N) -> Result<HashSet<N>, GraphError<N>> {
+        self.check_node(node)?;
 
-        visited_nodes.insert(node);
-        queue.push_back(node);
+        return Ok(self.depth_first_search(node));
+    }
 
-        while let Some(node_pop) = queue.pop_front() {
-            if node_pop == target {
-                return node_pop as i32;
-            }
+    /// Checks if `target` can be reached from `node` using breadth-first search. Returns `Some(target)` if it can, `None` otherwise.
+    /// See `breadth_first_traversal()` for hop distances and paths.
+    pub fn breadth_first_search(&self, node: N, target: N) -> Option<N> {
+        return self.breadth_first_search_filtered(node, target, |_, _, _| true);
+    }
 
-            for adjacent in self.get_node_adjacents(node_pop).iter() {
-                queue.push_back((*adjacent).0);
-                visited_nodes.insert((*adjacent).0);
-            }
-        }
+    /// Runs `breadth_first_search()` using only edges `(source, target, weight)` for which `is_allowed` returns `true`.
+    pub(crate) fn breadth_first_search_filtered(&self, node: N, target: N, is_allowed: impl Fn(N, N, E) -> bool) -> Option<N> {
+        let result = self.breadth_first_filtered(node, None, Some(target), is_allowed);
 
-        return -1;
+        return result.distances.contains_key(&target).then_some(target);
     }
 
-    /// Restores path from `source` to `target`. `parents` is a `HashMap<u32, i32>`, which stores a node and its predecessor.
+    /// Runs `breadth_first_search()`, returning `GraphError::UnknownNode` if `node` or `target` is not present in the graph.
+    pub fn try_breadth_first_search(&self, node: N, target: N) -> Result<Option<N>, GraphError<N>> {
+        self.check_node(node)?;
+        self.check_node(target)?;
+
+        return Ok(self.breadth_first_search(node, target));
+    }
+
+    /// Restores path from `source` to `target`. `parents` is a `HashMap<N, N>`, which stores a node and its predecessor.
+    ///
+    /// Panics if `parents` doesn't lead from `target` back to `source`, see `try_restore_path()`.
+    pub fn restore_path(&self, source: N, target: N, parents: HashMap<N, N>) -> VecDeque<N> {
+        return self
+            .try_restore_path(source, target, &parents)
+            .unwrap_or_else(|_| panic!("parents don't contain a path from source to target"));
+    }
 
-    pub fn restore_path(&self, source: u32, target: u32, parents: HashMap<u32, i32>) -> VecDeque<u32> {
-        let mut path: VecDeque<u32> = VecDeque::from([]);
+    /// Restores path from `source` to `target` like `restore_path()`.
+    /// Returns `GraphError::NoPath` if `parents` doesn't lead from `target` back to `source`.
+    pub fn try_restore_path(&self, source: N, target: N, parents: &HashMap<N, N>) -> Result<VecDeque<N>, GraphError<N>> {
+        let mut path: VecDeque<N> = VecDeque::from([target]);
         let mut current_node = target;
+
         while current_node != source {
-            path.push_front(current_node);
-            current_node = *parents.get(&current_node).unwrap() as u32;
+            match parents.get(&current_node) {
+                Some(&parent) if path.len() <= parents.len() => {
+                    path.push_front(parent);
+                    current_node = parent;
+                }
+                _ => return Err(GraphError::NoPath { source, target }),
+            }
         }
-        path.push_front(source);
-        return path;
+
+        return Ok(path);
     }
 
-    /// Finds the length of the shortest path from `source` to `target` using Dijkstra algorithm.
-    /// Returns `DijkstraResult`.
+    /// Checks if a graph is conected, ignoring directions of edges. Same as `is_weakly_connected()`.
+    /// Use `is_strongly_connected()` to take directions into account.
+    pub fn is_connected(&self) -> bool {
+        return self.is_weakly_connected();
+    }
 
-    pub fn shortest_path(&self, source: u32, target: u32) -> DijkstraResult {
-        if source == target {
-            return DijkstraResult { cost: Some(0), parents: Some(HashMap::from([(0, 0)])) }
+    /// Clears graph from edges.
+    pub fn clear(&mut self) -> &HashMap<N, Vec<(N, E)>> {
+        self.nodes.clear();
+        self.coordinates.clear();
+
+        return &self.nodes;
+    }
+
+    /// Returns a graph with the same nodes and coordinates, where every edge goes in the opposite direction.
+    pub fn reversed(&self) -> Self {
+        let mut reversed = Self::default();
+
+        for &node in self.nodes.keys() {
+            reversed.add_node(node);
         }
 
-        let mut queue: BinaryHeap<DijkstraState> = BinaryHeap::new();
-        let mut tentative_distances: HashMap<u32, i32> = HashMap::new();
-        let mut parents: HashMap<u32, i32> = HashMap::new();
+        for (&source, adjacents) in &self.nodes {
+            for &(target, weight) in adjacents {
+                if let Some(reverse_adjacents) = reversed.nodes.get_mut(&target) {
+                    reverse_adjacents.push((source, weight));
+                }
+            }
+        }
 
-        for node in self.nodes.keys() {
-            tentative_distances.insert(*node, i32::MAX);
-            parents.insert(*node, -1);
-        } // add all nodes to queue and distances HashMap with distance to them set to i32::MAX by default. 
+        reversed.coordinates = self.coordinates.clone();
 
-        queue.push(DijkstraState {node: source, cost: 0});
+        return reversed;
+    }
 
-        tentative_distances.entry(source).and_modify(|entry| {
-            *entry = 0;
-        }); // set distance to source to 0
-        parents.entry(source).and_modify(|entry| {
-            *entry = 0;
-        });
+    /// Returns all edges of the graph as `(source, target, weight, is_symmetric)`.
+    /// Edges which exist in both directions with the same weight (see `add_edge()`) are returned once with `is_symmetric` set.
+    pub(crate) fn edges_by_direction(&self) -> Vec<(N, N, E, bool)> {
+        let mut edges: Vec<(N, N, E, bool)> = Vec::new();
+        let mut written: HashMap<(N, N), Vec<E>> = HashMap::new();
+
+        for (&source, adjacents) in &self.nodes {
+            for &(target, weight) in adjacents {
+                let is_symmetric = self.nodes.get(&target).is_some_and(|reverse| reverse.contains(&(source, weight)));
+
+                if !is_symmetric {
+                    edges.push((source, target, weight, false));
+                } else if !written.get(&(source, target)).is_some_and(|weights| weights.contains(&weight)) {
+                    written.entry((target, source)).or_default().push(weight);
+                    edges.push((source, target, weight, true));
+                }
+            }
+        }
+
+        return edges;
+    }
+}
+
+impl<N: Copy + Eq + Hash, E: Weight> Graph<N, E> {
+    /// Finds the shortest path from `source` to `target` using Dijkstra algorithm.
+    /// Returns `DijkstraResult`, whose `path` contains the nodes of the path, weights of its edges and its cost.
+    pub fn shortest_path(&self, source: N, target: N) -> DijkstraResult<N, E> {
+        let (distances, parents) = self.dijkstra(source, Some(target));
+
+        let Some(&cost) = distances.get(&target) else {
+            return DijkstraResult { cost: None, parents: None, path: None };
+        };
+
+        return self.dijkstra_result(source, target, cost, parents);
+    }
+
+    /// Finds the shortest paths from `source` to every node reachable from it using Dijkstra algorithm.
+    /// Returns `ShortestPathTree`, which can restore the path to any of those nodes without searching again.
+    pub fn shortest_path_tree(&self, source: N) -> ShortestPathTree<N, E> {
+        let (distances, parents) = self.dijkstra(source, None);
+
+        return ShortestPathTree { source, distances, parents };
+    }
+
+    /// Finds the shortest paths from `source` like `shortest_path_tree()`.
+    /// Returns `GraphError::UnknownNode` if `source` is not present in the graph.
+    pub fn try_shortest_path_tree(&self, source: N) -> Result<ShortestPathTree<N, E>, GraphError<N>> {
+        self.check_node(source)?;
+
+        return Ok(self.shortest_path_tree(source));
+    }
+
+    /// Runs Dijkstra algorithm from `source`. Stops as soon as `target` is reached, or reaches every node it can if `target` is `None`.
+    /// Returns distances to reached nodes and a map of a node and its predecessor together with the weight of the edge between them.
+    pub(crate) fn dijkstra(&self, source: N, target: Option<N>) -> (HashMap<N, E>, HashMap<N, (N, E)>) {
+        return self.dijkstra_filtered(source, target, |_, _, _| true);
+    }
+
+    /// Runs Dijkstra algorithm like `dijkstra()`, using only edges `(source, target, weight)` for which `is_allowed` returns `true`.
+    /// Lets algorithms hide parts of the graph without changing it.
+    pub(crate) fn dijkstra_filtered(&self, source: N, target: Option<N>, is_allowed: impl Fn(N, N, E) -> bool) -> (HashMap<N, E>, HashMap<N, (N, E)>) {
+        let mut queue: BinaryHeap<DijkstraState<N, E>> = BinaryHeap::new();
+        let mut tentative_distances: HashMap<N, E> = HashMap::new();
+        let mut parents: HashMap<N, (N, E)> = HashMap::new();
+
+        queue.push(DijkstraState {node: source, cost: E::zero()});
+        tentative_distances.insert(source, E::zero()); // set distance to source to 0, nodes which are not in the map are not reached yet
 
         while let Some(DijkstraState {node, cost}) = queue.pop() {
-            if node == target {
-                return DijkstraResult { cost: Some(cost as u32), parents: Some(parents) };
+            if Some(node) == target {
+                break;
             }
-            if cost > *tentative_distances.get(&node).unwrap() {
+            if cost > tentative_distances[&node] {
                 continue;
             }
 
-            for node_tuple in &self.nodes[&node] {
-                let next: DijkstraState = DijkstraState { node: node_tuple.0, cost: node_tuple.1 as i32 + cost };
+            for &(adjacent, weight) in self.adjacents(node).filter(|&&(adjacent, weight)| is_allowed(node, adjacent, weight)) {
+                let Some(next_cost) = cost.checked_add(weight) else {
+                    continue;
+                };
+                let next: DijkstraState<N, E> = DijkstraState { node: adjacent, cost: next_cost };
 
-                if next.cost < *tentative_distances.get(&next.node).unwrap() {
+                if tentative_distances.get(&next.node).is_none_or(|&distance| next.cost < distance) {
                     queue.push(next);
-                    tentative_distances.entry(next.node).and_modify(|entry| {
-                        *entry = next.cost;
-                    });
-                    parents.entry(next.node).and_modify(|entry| {
-                        *entry = node as i32;
-                    });
+                    tentative_distances.insert(next.node, next.cost);
+                    parents.insert(next.node, (node, weight));
                 }
             }
         }
 
-        return DijkstraResult {cost: None, parents: None} ;
+        return (tentative_distances, parents);
     }
 
-    pub fn manhattan_distance(&self, source: (u32, u32), target: (u32, u32)) -> u32 {
-        return source.0.abs_diff(target.0) + source.1.abs_diff(target.1)
-    }
+    /// Builds a `DijkstraResult` of a search which reached `target`, restoring the path from `parents`.
+    pub(crate) fn dijkstra_result(&self, source: N, target: N, cost: E, parents: HashMap<N, (N, E)>) -> DijkstraResult<N, E> {
+        let path = path_from_parents(source, target, &parents);
+        let parents = parents.into_iter().map(|(node, (parent, _))| (node, parent)).collect();
 
-    pub fn euclidian_distance(&self, source: (u32, u32), target: (u32, u32)) -> f32 {
-        return (source.0.abs_diff(target.0).pow(2) as f32 + source.1.abs_diff(target.1).pow(2) as f32).sqrt()
+        return DijkstraResult { cost: Some(cost), parents: Some(parents), path };
     }
 
-    pub fn astar(&self, source: u32, target: u32, heuristic: impl Fn(u32, u32) -> u32) -> u32 {
-        if source == target {
-            return 0;
+    /// Finds the shortest path from `source` to `target` like `shortest_path()`.
+    /// Returns `GraphError::UnknownNode` if `source` or `target` is not present in the graph and `GraphError::NoPath` if `target` can't be reached.
+    pub fn try_shortest_path(&self, source: N, target: N) -> Result<DijkstraResult<N, E>, GraphError<N>> {
+        self.check_node(source)?;
+        self.check_node(target)?;
+
+        let result = self.shortest_path(source, target);
+
+        if result.cost.is_none() {
+            return Err(GraphError::NoPath { source, target });
         }
 
-        let mut queue: BinaryHeap<DijkstraState> = BinaryHeap::new();
-        let mut tentative_distances: HashMap<u32, i32> = HashMap::new();
-        let mut parents: HashMap<u32, i32> = HashMap::new();
+        return Ok(result);
+    }
 
-        for node in self.nodes.keys() {
-            tentative_distances.insert(*node, i32::MAX);
-            parents.insert(*node, -1);
-        } // add all nodes to queue and distances HashMap with distance to them set to i32::MAX by default. 
+    /// Finds the shortest path from `source` to `target` using A* algorithm. Returns the same result as `shortest_path()`.
+    /// `heuristic(node, target)` estimates the cost of the path from `node` to `target`, it has to never overestimate it (be admissible),
+    /// otherwise the found path may not be the shortest one. See `manhattan_heuristic()` and `euclidian_heuristic()` for heuristics based on node coordinates.
+    pub fn astar(&self, source: N, target: N, heuristic: impl Fn(N, N) -> E) -> DijkstraResult<N, E> {
+        let (distances, parents) = self.astar_search(source, target, heuristic, |_, _, _| true);
 
-        // reweighting the graph...
+        let Some(&cost) = distances.get(&target) else {
+            return DijkstraResult { cost: None, parents: None, path: None };
+        };
 
-        queue.push(DijkstraState {node: source, cost: 0});
+        return self.dijkstra_result(source, target, cost, parents);
+    }
+
+    /// Finds the shortest path from `source` to `target` like `astar()`.
+    /// Returns `GraphError::UnknownNode` if `source` or `target` is not present in the graph and `GraphError::NoPath` if `target` can't be reached.
+    pub fn try_astar(&self, source: N, target: N, heuristic: impl Fn(N, N) -> E) -> Result<DijkstraResult<N, E>, GraphError<N>> {
+        self.check_node(source)?;
+        self.check_node(target)?;
+
+        let result = self.astar(source, target, heuristic);
+
+        if result.cost.is_none() {
+            return Err(GraphError::NoPath { source, target });
+        }
+
+        return Ok(result);
+    }
 
-        tentative_distances.entry(source).and_modify(|entry| {
-            *entry = 0;
-        }); // set distance to source to 0
-        parents.entry(source).and_modify(|entry| {
-            *entry = 0;
+    /// Builds an A* heuristic which returns the Manhattan distance between grid coordinates of nodes, given by `coordinates(node)`.
+    /// Shorthand for `coordinate_heuristic()` with `Heuristic::Manhattan`. The heuristic is admissible if moving from a node to its adjacent
+    /// costs at least the Manhattan distance between them, e.g. on a 4-connected grid.
+    pub fn manhattan_heuristic<'a>(&'a self, coordinates: impl Fn(N) -> (u32, u32) + 'a) -> impl Fn(N, N) -> E + 'a {
+        return self.coordinate_heuristic(Heuristic::Manhattan, move |node| {
+            let (x, y) = coordinates(node);
+            return Some(Coordinates::new(x as f64, y as f64));
         });
+    }
+
+    /// Builds an A* heuristic which returns the straight-line distance between grid coordinates of nodes, given by `coordinates(node)`, rounded down.
+    /// Shorthand for `coordinate_heuristic()` with `Heuristic::Euclidean`. The heuristic is admissible if moving from a node to its adjacent
+    /// costs at least the straight-line distance between them.
+    pub fn euclidian_heuristic<'a>(&'a self, coordinates: impl Fn(N) -> (u32, u32) + 'a) -> impl Fn(N, N) -> E + 'a {
+        return self.coordinate_heuristic(Heuristic::Euclidean, move |node| {
+            let (x, y) = coordinates(node);
+            return Some(Coordinates::new(x as f64, y as f64));
+        });
+    }
+
+    /// Runs A* algorithm from `source` until `target` is reached, using only edges for which `is_allowed` returns `true`.
+    /// Returns the same maps as `dijkstra()`, `target` is present in them only if it was reached.
+    pub(crate) fn astar_search(&self, source: N, target: N, heuristic: impl Fn(N, N) -> E, is_allowed: impl Fn(N, N, E) -> bool) -> (HashMap<N, E>, HashMap<N, (N, E)>) {
+        let mut queue: BinaryHeap<DijkstraState<N, E>> = BinaryHeap::new();
+        let mut tentative_distances: HashMap<N, E> = HashMap::new();
+        let mut parents: HashMap<N, (N, E)> = HashMap::new();
+
+        queue.push(DijkstraState {node: source, cost: heuristic(source, target)});
+        tentative_distances.insert(source, E::zero()); // set distance to source to 0
 
         while let Some(DijkstraState {node, cost}) = queue.pop() {
             if node == target {
-                return *tentative_distances.get(&node).unwrap() as u32;
+                break;
             }
-            
-            if cost > *tentative_distances.get(&node).unwrap() {
+
+            if tentative_distances[&node].checked_add(heuristic(node, target)).is_some_and(|estimate| cost > estimate) {
                 continue;
             }
 
-            for node_tuple in &self.nodes[&node] { // for adjacent node to our node that we are observing
-                let next_cost = node_tuple.1 as i32 + cost;
-                let next_node = node_tuple.0;
-
-                if next_cost < *tentative_distances.get(&next_node).unwrap() {
-                    queue.push(DijkstraState { node: next_node, cost: next_cost + heuristic(next_node, target) as i32 });
-                    tentative_distances.entry(next_node).and_modify(|entry| {
-                        *entry = next_cost;
-                    });
-                    parents.entry(next_node).and_modify(|entry| {
-                        *entry = node as i32;
-                    });
+            for &(next_node, weight) in self.adjacents(node).filter(|&&(next_node, weight)| is_allowed(node, next_node, weight)) { // for adjacent node to our node that we are observing
+                let Some(next_cost) = tentative_distances[&node].checked_add(weight) else {
+                    continue;
+                };
+                // with an admissible heuristic, an estimate which overflows means that target can't be reached through this node without overflowing either
+                let Some(estimate) = next_cost.checked_add(heuristic(next_node, target)) else {
+                    continue;
+                };
+
+                if tentative_distances.get(&next_node).is_none_or(|&distance| next_cost < distance) {
+                    queue.push(DijkstraState { node: next_node, cost: estimate });
+                    tentative_distances.insert(next_node, next_cost);
+                    parents.insert(next_node, (node, weight));
                 }
             }
         }
-    
-        return 0;
+
+        return (tentative_distances, parents);
+    }
+
+    /// Finds the shortest paths from `source` to every reachable node using Bellman-Ford algorithm. Negative weights are allowed.
+    /// If a cycle of negative weight can be reached from `source`, it's returned in `negative_cycle` and the distances are not meaningful.
+    pub fn bellman_ford(&self, source: N) -> BellmanFordResult<N, E> {
+        return self.bellman_ford_from(source, HashMap::from([(source, E::zero())]));
     }
 
-    pub fn bellman_ford(&self, source: u32) -> HashMap<u32, i32> {
-        let mut distances: HashMap<u32, i32> = HashMap::new();
+    /// Runs Bellman-Ford algorithm starting from the given tentative `distances`, paths of the result start at `source`.
+    /// Starting with every node at distance 0 is the same as starting from a virtual node connected to every node with 0-weight edges.
+    pub(crate) fn bellman_ford_from(&self, source: N, mut distances: HashMap<N, E>) -> BellmanFordResult<N, E> {
+        let mut parents: HashMap<N, (N, E)> = HashMap::new();
 
-        for node in self.nodes.keys() {
-            distances.insert(*node, i32::MAX);
+        for _ in 0..self.vertex_count().saturating_sub(1) {
+            // nothing changed during the whole pass, so nothing will change during the next ones either
+            if self.relax_edges(&mut distances, &mut parents).is_none() {
+                return BellmanFordResult { source, distances, parents, negative_cycle: None };
+            }
         }
 
-        distances.insert(source, 0);
+        // if an edge can still be relaxed, the node it leads to is reached through a negative cycle
+        let negative_cycle = self.relax_edges(&mut distances, &mut parents).and_then(|node| self.negative_cycle_from(node, &parents));
 
-        for _ in 0..self.nodes.len() - 1 {
-            for node in &self.nodes {
-                for edge in node.1 {
-                    let target = edge.0;
-                    let distance = edge.1;
+        return BellmanFordResult { source, distances, parents, negative_cycle };
+    }
 
-                    let potential_distance = distances.get(&node.0).unwrap().saturating_add(distance);
+    /// Finds the shortest paths from `source` to every reachable node using the queue-based variant of Bellman-Ford algorithm (SPFA).
+    /// Only edges of nodes whose distance has just changed are relaxed, which is usually much faster. Returns the same result as `bellman_ford()`.
+    pub fn spfa(&self, source: N) -> BellmanFordResult<N, E> {
+        let vertex_count = self.vertex_count();
+        let mut distances: HashMap<N, E> = HashMap::from([(source, E::zero())]);
+        let mut parents: HashMap<N, (N, E)> = HashMap::new();
+        let mut edge_counts: HashMap<N, usize> = HashMap::from([(source, 0)]);
+        let mut queue: VecDeque<N> = VecDeque::from([source]);
+        let mut queued: HashSet<N> = HashSet::from([source]);
+
+        while let Some(node) = queue.pop_front() {
+            queued.remove(&node);
+
+            let node_distance = distances[&node];
+            let node_edge_count = edge_counts[&node];
+
+            for &(target, weight) in self.adjacents(node) {
+                let Some(potential_distance) = node_distance.checked_add(weight) else {
+                    continue;
+                };
+
+                if distances.get(&target).is_some_and(|&current| current <= potential_distance) {
+                    continue;
+                }
 
-                    println!("{:#?}", target);
+                distances.insert(target, potential_distance);
+                parents.insert(target, (node, weight));
+                edge_counts.insert(target, node_edge_count + 1);
 
-                    if distances.get(&target).unwrap() > &potential_distance {
-                        distances.entry(target).and_modify(|dist| {
-                            *dist = potential_distance;
-                        });
+                // a shortest path has fewer edges than there are nodes, a longer one has to go through a negative cycle
+                if node_edge_count + 1 >= vertex_count {
+                    if let Some(negative_cycle) = self.negative_cycle_from(target, &parents) {
+                        return BellmanFordResult { source, distances, parents, negative_cycle: Some(negative_cycle) };
                     }
                 }
+
+                if queued.insert(target) {
+                    queue.push_back(target);
+                }
             }
         }
 
-        return distances;
+        return BellmanFordResult { source, distances, parents, negative_cycle: None };
     }
 
-    pub fn has_negative_cycle(&self, distances: &HashMap<u32, i32>) -> bool {
-        let new_distances = distances.clone();
-        for node in &self.nodes {
-            for edge in node.1 {
-                let target = edge.0;
-                let distance = edge.1;
+    /// Finds the shortest paths from `source` like `spfa()`.
+    /// Returns `GraphError::UnknownNode` if `source` is not present in the graph and `GraphError::NegativeCycle` if a negative cycle can be reached from it.
+    pub fn try_spfa(&self, source: N) -> Result<ShortestPathTree<N, E>, GraphError<N>> {
+        self.check_node(source)?;
 
-                let potential_distance = new_distances.get(&node.0).unwrap().saturating_add(distance);
+        return self.spfa(source).into_tree();
+    }
 
-                if new_distances.get(&target).unwrap() > &potential_distance {
-                    return true;
-                }
+    /// Returns the negative cycle `node` was reached through, following `parents` back from it.
+    fn negative_cycle_from(&self, node: N, parents: &HashMap<N, (N, E)>) -> Option<Vec<N>> {
+        let vertex_count = self.vertex_count();
+
+        // going back as many times as there are nodes surely ends up on the cycle
+        let mut cycle_node = node;
+        for _ in 0..vertex_count {
+            cycle_node = parents.get(&cycle_node)?.0;
+        }
+
+        let mut cycle: Vec<N> = vec![cycle_node];
+        let mut current_node = parents.get(&cycle_node)?.0;
+
+        while current_node != cycle_node {
+            if cycle.len() > vertex_count {
+                return None;
             }
+
+            cycle.push(current_node);
+            current_node = parents.get(&current_node)?.0;
         }
 
-        print!("{:#?} : {:#?}", new_distances, distances);
+        cycle.reverse();
 
-        return false;
+        return Some(cycle);
+    }
 
+    /// Counts nodes of the graph together with targets of edges which were never added as nodes.
+    fn vertex_count(&self) -> usize {
+        let missing_targets: HashSet<N> = self.nodes.values().flatten().map(|&(target, _)| target).filter(|target| !self.nodes.contains_key(target)).collect();
+
+        return self.nodes.len() + missing_targets.len();
     }
 
+    /// Relaxes every edge once. Returns the last node whose distance was lowered, or `None` if no distance changed.
+    fn relax_edges(&self, distances: &mut HashMap<N, E>, parents: &mut HashMap<N, (N, E)>) -> Option<N> {
+        let mut relaxed_node: Option<N> = None;
 
-    /// Checks if a graph is conected. 
-    /// Conected graph is a graph where there are no nodes with less than one adjacent node.
+        for (&node, adjacents) in &self.nodes {
+            let Some(&node_distance) = distances.get(&node) else {
+                continue;
+            };
 
-    pub fn is_connected(&self) -> bool {
-        let visited_nodes = self.depth_first_search(0);
-        if visited_nodes.len() < self.nodes.len() {
-            return false;
+            for &(target, weight) in adjacents {
+                let Some(potential_distance) = node_distance.checked_add(weight) else {
+                    continue;
+                };
+
+                if distances.get(&target).is_none_or(|&current| current > potential_distance) {
+                    distances.insert(target, potential_distance);
+                    parents.insert(target, (node, weight));
+                    relaxed_node = Some(target);
+                }
+            }
         }
-        return true;
+
+        return relaxed_node;
     }
 
-    /// Serializes a graph into JSON format. (uses serde)
+    /// Finds the shortest paths from `source` like `bellman_ford()`.
+    /// Returns `GraphError::UnknownNode` if `source` is not present in the graph and `GraphError::NegativeCycle` if a negative cycle can be reached from it.
+    pub fn try_bellman_ford(&self, source: N) -> Result<ShortestPathTree<N, E>, GraphError<N>> {
+        self.check_node(source)?;
 
-    pub fn serialize(&self) -> String {
-        return serde_json::to_string(&self.nodes).unwrap();
+        return self.bellman_ford(source).into_tree();
     }
 
-    /// Clears graph from edges.
+    /// Checks if the graph contains a cycle of negative weight anywhere, not only reachable from a certain node.
+    pub fn has_negative_cycle(&self) -> bool {
+        return self.find_negative_cycle().is_some();
+    }
 
-    pub fn clear(&mut self) -> &HashMap<u32, HashSet<(u32, i32)>> {
-        let _ = &self.nodes.clear();
+    /// Finds a cycle of negative weight anywhere in the graph. Returns its nodes in order, the last node has an edge to the first one.
+    pub fn find_negative_cycle(&self) -> Option<Vec<N>> {
+        let &source = self.nodes.keys().next()?;
 
-        return &self.nodes;
+        return self.bellman_ford_from(source, self.nodes.keys().map(|&node| (node, E::zero())).collect()).negative_cycle;
     }
 }
@@ -0,0 +1,367 @@
+use crate::{Coordinates, Graph, NodeIds, ParseError};
+use quick_xml::escape::escape;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::io;
+use std::io::{BufRead, Write};
+use std::str::FromStr;
+
+/// Name of the GraphML data key which stores edge weights.
+const WEIGHT_KEY: &str = "weight";
+
+/// Names of the GraphML data keys which store node coordinates, the same ones Gephi uses.
+const COORDINATE_KEYS: [&str; 3] = ["x", "y", "z"];
+
+struct GraphmlKey {
+    domain: String,
+    name: Option<String>,
+    default: Option<String>,
+}
+
+struct GraphmlNode {
+    id: String,
+    data: HashMap<String, String>,
+}
+
+struct GraphmlEdge {
+    source: String,
+    target: String,
+    directed: Option<bool>,
+    data: HashMap<String, String>,
+}
+
+/// Nodes and edges of a GraphML document, before node ids and weights are parsed.
+struct GraphmlDocument {
+    keys: HashMap<String, GraphmlKey>,
+    nodes: Vec<GraphmlNode>,
+    edges: Vec<GraphmlEdge>,
+    edge_default_directed: bool,
+}
+
+impl<N: Copy + Eq + Hash, E: Copy + PartialEq> Graph<N, E> {
+    /// Exports a graph into GraphML format.
+    /// Edges which exist in both directions with the same weight (see `add_edge()`) are written as undirected edges,
+    /// all other edges are written as directed. Weights are stored in the `weight` data key, declared as `long` if all of them are integers and `double` otherwise.
+    /// Node coordinates are stored in the `x`, `y` and `z` data keys.
+    pub fn to_graphml(&self) -> String
+    where
+        N: Display,
+        E: Display,
+    {
+        let mut buffer: Vec<u8> = Vec::new();
+        self.write_graphml(&mut buffer).unwrap();
+
+        return String::from_utf8(buffer).unwrap();
+    }
+
+    /// Exports a graph into GraphML format and writes it to `writer`. Produces the same output as `to_graphml()`.
+    pub fn write_graphml<W: Write>(&self, mut writer: W) -> io::Result<()>
+    where
+        N: Display,
+        E: Display,
+    {
+        let (undirected, directed): (Vec<_>, Vec<_>) = self.edges_by_direction().into_iter().partition(|&(_, _, _, is_symmetric)| is_symmetric);
+
+        let edge_default = if undirected.len() > directed.len() { "undirected" } else { "directed" };
+        let weight_type = if self.nodes.values().flatten().all(|(_, weight)| weight.to_string().parse::<i64>().is_ok()) { "long" } else { "double" };
+
+        writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(writer, r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="http://graphml.graphdrawing.org/xmlns http://graphml.graphdrawing.org/xmlns/1.0/graphml.xsd">"#)?;
+        writeln!(writer, r#"  <key id="{0}" for="edge" attr.name="{0}" attr.type="{1}"/>"#, WEIGHT_KEY, weight_type)?;
+
+        if !self.coordinates.is_empty() {
+            for key in COORDINATE_KEYS {
+                writeln!(writer, r#"  <key id="{0}" for="node" attr.name="{0}" attr.type="double"/>"#, key)?;
+            }
+        }
+
+        writeln!(writer, r#"  <graph id="G" edgedefault="{}">"#, edge_default)?;
+
+        for node in self.nodes.keys() {
+            let Some(coordinates) = self.coordinates.get(node) else {
+                writeln!(writer, r#"    <node id="{}"/>"#, escape(&node.to_string()))?;
+                continue;
+            };
+
+            writeln!(writer, r#"    <node id="{}">"#, escape(&node.to_string()))?;
+
+            for (key, value) in COORDINATE_KEYS.iter().zip([coordinates.x, coordinates.y, coordinates.z]) {
+                writeln!(writer, r#"      <data key="{}">{}</data>"#, key, value)?;
+            }
+
+            writeln!(writer, "    </node>")?;
+        }
+
+        for (edges, is_directed) in [(&undirected, false), (&directed, true)] {
+            let direction = if (edge_default == "directed") == is_directed {
+                ""
+            } else if is_directed {
+                r#" directed="true""#
+            } else {
+                r#" directed="false""#
+            };
+
+            for (source, target, weight, _) in edges {
+                writeln!(writer, r#"    <edge source="{}" target="{}"{}>"#, escape(&source.to_string()), escape(&target.to_string()), direction)?;
+                writeln!(writer, r#"      <data key="{}">{}</data>"#, WEIGHT_KEY, escape(&weight.to_string()))?;
+                writeln!(writer, "    </edge>")?;
+            }
+        }
+
+        writeln!(writer, "  </graph>")?;
+        writeln!(writer, "</graphml>")?;
+
+        return Ok(());
+    }
+
+    /// Imports a graph from GraphML.
+    /// Undirected edges are added with `add_edge()`, directed ones with `add_edge_directed()`.
+    /// Edge weights are read from the data key named `weight`, falling back to the key's default value and then to `E::default()`.
+    /// Node coordinates are read from the data keys named `x`, `y` and optionally `z`.
+    /// Tools like Gephi and yEd store weights as doubles, so whole numbers such as `1.0` are accepted for integer weights.
+    /// Returns `ParseError` if the document is malformed or if a node id or weight can't be parsed. Use `from_graphml_with_ids()`
+    /// for documents with node ids which aren't numbers, like `n0` written by yEd.
+    pub fn from_graphml(graphml: &str) -> Result<Self, ParseError>
+    where
+        N: FromStr,
+        E: FromStr + Default,
+    {
+        return Self::read_graphml(graphml.as_bytes());
+    }
+
+    /// Imports a graph from GraphML like `from_graphml()`, using `default_weight` for edges without a weight.
+    pub fn from_graphml_with_default_weight(graphml: &str, default_weight: E) -> Result<Self, ParseError>
+    where
+        N: FromStr,
+        E: FromStr,
+    {
+        let document = parse_graphml(graphml.as_bytes())?;
+
+        return Self::from_graphml_document(document, default_weight, |id| id.parse().map_err(|_| ParseError::Invalid(format!("invalid node id: {}", id))));
+    }
+
+    /// Imports a graph from GraphML, reading it from `reader`. See `from_graphml()`.
+    pub fn read_graphml<R: BufRead>(reader: R) -> Result<Self, ParseError>
+    where
+        N: FromStr,
+        E: FromStr + Default,
+    {
+        let document = parse_graphml(reader)?;
+
+        return Self::from_graphml_document(document, E::default(), |id| id.parse().map_err(|_| ParseError::Invalid(format!("invalid node id: {}", id))));
+    }
+
+    /// Builds a graph from a parsed GraphML document, turning node ids into nodes with `node`.
+    fn from_graphml_document(document: GraphmlDocument, default_weight: E, mut node: impl FnMut(&str) -> Result<N, ParseError>) -> Result<Self, ParseError>
+    where
+        E: FromStr,
+    {
+        let GraphmlDocument { keys, nodes: graphml_nodes, edges, edge_default_directed } = document;
+
+        let weight_key = find_key(&keys, "edge", WEIGHT_KEY);
+        let coordinate_keys = COORDINATE_KEYS.map(|name| find_key(&keys, "node", name));
+
+        let mut graph = Self::default();
+        let mut nodes: HashMap<String, N> = HashMap::new();
+
+        for graphml_node in graphml_nodes {
+            let node = node(&graphml_node.id)?;
+            graph.add_node(node);
+
+            let [x, y, z] = coordinate_keys.map(|key| key.and_then(|(id, key)| graphml_node.data.get(id).or(key.default.as_ref())));
+
+            if let (Some(x), Some(y)) = (x, y) {
+                let parse = |value: &String| value.parse::<f64>().map_err(|_| ParseError::Invalid(format!("invalid coordinate of node {}: {}", graphml_node.id, value)));
+                let z = z.map(parse).transpose()?.unwrap_or(0.0);
+
+                graph.set_coordinates(node, Coordinates::new_3d(parse(x)?, parse(y)?, z));
+            }
+
+            nodes.insert(graphml_node.id, node);
+        }
+
+        for edge in edges {
+            let weight: E = match weight_key.and_then(|(id, key)| edge.data.get(id).or(key.default.as_ref())) {
+                Some(weight) => parse_weight(weight).ok_or_else(|| ParseError::Invalid(format!("invalid edge weight: {}", weight)))?,
+                None => default_weight,
+            };
+
+            let (Some(&source), Some(&target)) = (nodes.get(&edge.source), nodes.get(&edge.target)) else {
+                return Err(ParseError::DanglingEdge { source: edge.source, target: edge.target });
+            };
+
+            if edge.directed.unwrap_or(edge_default_directed) {
+                graph.add_edge_directed(source, target, weight);
+            } else {
+                graph.add_edge(source, target, weight);
+            }
+        }
+
+        return Ok(graph);
+    }
+}
+
+impl<E: Copy + PartialEq> Graph<usize, E> {
+    /// Imports a graph from GraphML with node ids of any form, e.g. `n0` written by yEd.
+    /// Nodes are numbered from 0 in the order they appear in the document, the returned `NodeIds` maps them back to their GraphML ids.
+    /// Edges without a weight get `default_weight`, otherwise the document is read like in `from_graphml()`.
+    pub fn from_graphml_with_ids(graphml: &str, default_weight: E) -> Result<(Self, NodeIds<String>), ParseError>
+    where
+        E: FromStr,
+    {
+        let document = parse_graphml(graphml.as_bytes())?;
+        let mut ids: NodeIds<String> = NodeIds::new();
+
+        let graph = Self::from_graphml_document(document, default_weight, |id| Ok(ids.insert(id.to_string())))?;
+
+        return Ok((graph, ids));
+    }
+}
+
+/// Reads keys, node ids and edges of a GraphML document from `reader`.
+fn parse_graphml<R: BufRead>(reader: R) -> Result<GraphmlDocument, ParseError> {
+    let mut reader = Reader::from_reader(reader);
+    let mut buffer: Vec<u8> = Vec::new();
+
+    let mut keys: HashMap<String, GraphmlKey> = HashMap::new();
+    let mut nodes: Vec<GraphmlNode> = Vec::new();
+    let mut edges: Vec<GraphmlEdge> = Vec::new();
+
+    let mut edge_default_directed = true;
+    let mut current_key: Option<String> = None;
+    let mut current_node: Option<usize> = None;
+    let mut current_edge: Option<GraphmlEdge> = None;
+    let mut current_data: Option<String> = None;
+    let mut in_default = false;
+    let mut text = String::new();
+
+    loop {
+        let event = reader.read_event_into(&mut buffer)?;
+        let is_empty = matches!(event, Event::Empty(_));
+
+        match event {
+            Event::Start(element) | Event::Empty(element) => {
+                match element.local_name().as_ref() {
+                    b"graph" => {
+                        edge_default_directed = attribute(&element, "edgedefault")?.as_deref() != Some("undirected");
+                    }
+                    b"key" => {
+                        let id = required_attribute(&element, "id")?;
+                        keys.insert(id.clone(), GraphmlKey {
+                            domain: attribute(&element, "for")?.unwrap_or_else(|| String::from("all")),
+                            name: attribute(&element, "attr.name")?,
+                            default: None,
+                        });
+                        if !is_empty {
+                            current_key = Some(id);
+                        }
+                    }
+                    b"default" if current_key.is_some() && !is_empty => {
+                        in_default = true;
+                        text.clear();
+                    }
+                    b"node" => {
+                        nodes.push(GraphmlNode { id: required_attribute(&element, "id")?, data: HashMap::new() });
+                        if !is_empty {
+                            current_node = Some(nodes.len() - 1);
+                        }
+                    }
+                    b"edge" => {
+                        let directed = match attribute(&element, "directed")?.as_deref() {
+                            Some("true") => Some(true),
+                            Some("false") => Some(false),
+                            Some(other) => return Err(ParseError::Invalid(format!("invalid value of edge attribute directed: {}", other))),
+                            None => None,
+                        };
+                        let edge = GraphmlEdge {
+                            source: required_attribute(&element, "source")?,
+                            target: required_attribute(&element, "target")?,
+                            directed,
+                            data: HashMap::new(),
+                        };
+
+                        if is_empty {
+                            edges.push(edge);
+                        } else {
+                            current_edge = Some(edge);
+                        }
+                    }
+                    b"data" if (current_node.is_some() || current_edge.is_some()) && !is_empty => {
+                        current_data = Some(required_attribute(&element, "key")?);
+                        text.clear();
+                    }
+                    _ => {}
+                }
+            }
+            Event::Text(content) if in_default || current_data.is_some() => {
+                text.push_str(&content.unescape()?);
+            }
+            Event::End(element) => {
+                match element.local_name().as_ref() {
+                    b"key" => current_key = None,
+                    b"default" if in_default => {
+                        in_default = false;
+                        if let Some(key) = current_key.as_ref().and_then(|id| keys.get_mut(id)) {
+                            key.default = Some(text.trim().to_string());
+                        }
+                    }
+                    b"data" => {
+                        if let Some(key) = current_data.take() {
+                            if let Some(edge) = current_edge.as_mut() {
+                                edge.data.insert(key, text.trim().to_string());
+                            } else if let Some(node) = current_node.map(|index| &mut nodes[index]) {
+                                node.data.insert(key, text.trim().to_string());
+                            }
+                        }
+                    }
+                    b"node" => current_node = None,
+                    b"edge" => edges.extend(current_edge.take()),
+                    _ => {}
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+
+        buffer.clear();
+    }
+
+    return Ok(GraphmlDocument { keys, nodes, edges, edge_default_directed });
+}
+
+/// Finds the data key for `domain` elements whose name (or id, if it has no name) is `name`.
+fn find_key<'a>(keys: &'a HashMap<String, GraphmlKey>, domain: &str, name: &str) -> Option<(&'a String, &'a GraphmlKey)> {
+    return keys.iter().find(|(id, key)| (key.domain == domain || key.domain == "all") && key.name.as_deref().unwrap_or(id) == name);
+}
+
+/// Parses a weight, falling back to a whole number written as a double (e.g. `1.0`) for integer weight types.
+fn parse_weight<E: FromStr>(text: &str) -> Option<E> {
+    if let Ok(weight) = text.parse() {
+        return Some(weight);
+    }
+
+    let value: f64 = text.parse().ok().filter(|value: &f64| value.is_finite() && value.fract() == 0.0)?;
+
+    return format!("{:.0}", value).parse().ok();
+}
+
+fn attribute(element: &BytesStart, name: &str) -> Result<Option<String>, ParseError> {
+    for attribute in element.attributes() {
+        let attribute = attribute.map_err(quick_xml::Error::from)?;
+
+        if attribute.key.local_name().as_ref() == name.as_bytes() || attribute.key.as_ref() == name.as_bytes() {
+            return Ok(Some(attribute.unescape_value()?.into_owned()));
+        }
+    }
+
+    return Ok(None);
+}
+
+fn required_attribute(element: &BytesStart, name: &str) -> Result<String, ParseError> {
+    return attribute(element, name)?.ok_or_else(|| {
+        ParseError::Invalid(format!("<{}> element has no {} attribute", String::from_utf8_lossy(element.local_name().as_ref()), name))
+    });
+}
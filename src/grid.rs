@@ -0,0 +1,104 @@
+use crate::{Coordinates, Graph, GraphError, Weight};
+use std::hash::Hash;
+
+/// Moves allowed between cells of a grid, see `Graph::from_grid()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Connectivity {
+    /// Moves to the 4 cells sharing a side.
+    #[default]
+    Four,
+    /// Moves to the 8 cells sharing a side or a corner.
+    Eight,
+}
+
+/// Options for building a graph from a grid, see `Graph::from_grid()`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridOptions {
+    /// Moves allowed between cells. `Connectivity::Four` by default.
+    pub connectivity: Connectivity,
+    /// Cost of a diagonal move relative to a straight move into the same cell, √2 by default. The resulting weight is rounded up.
+    pub diagonal_factor: f64,
+    /// If `false` (default), a diagonal move is allowed only if both cells sharing a side with the source and the target are passable.
+    pub cut_corners: bool,
+}
+
+impl Default for GridOptions {
+    fn default() -> Self {
+        Self {
+            connectivity: Connectivity::Four,
+            diagonal_factor: std::f64::consts::SQRT_2,
+            cut_corners: false,
+        }
+    }
+}
+
+impl<N: Copy + Eq + Hash + TryFrom<usize>, E: Weight> Graph<N, E> {
+    /// Builds a graph from a 2D cost map, given as rows of cells. `None` is a blocked cell, `Some(cost)` is the cost of a straight move into the cell.
+    /// Cell in row `y` and column `x` becomes node `y * width + x` with coordinates `(x, y)`, blocked cells are not added to the graph.
+    /// Coordinates are set, so `astar()` can use `heuristic()`: `Heuristic::Manhattan` for 4-connected grids and `Heuristic::Octile` for 8-connected ones,
+    /// scaled by the smallest cell cost.
+    /// Returns `GraphError::InvalidInput` if rows have different lengths or if a node id doesn't fit into the node type.
+    pub fn from_grid(cells: &[Vec<Option<E>>], options: &GridOptions) -> Result<Self, GraphError<N>> {
+        let width = cells.first().map_or(0, |row| row.len());
+
+        if cells.iter().any(|row| row.len() != width) {
+            return Err(GraphError::InvalidInput(String::from("all rows of a grid have to be of the same length")));
+        }
+
+        let node_at = |x: usize, y: usize| -> Result<N, GraphError<N>> {
+            return N::try_from(y * width + x).map_err(|_| GraphError::InvalidInput(format!("cell ({}, {}) doesn't fit into the node type", x, y)));
+        };
+        let cost_at = |x: isize, y: isize| -> Option<E> {
+            let row = cells.get(usize::try_from(y).ok()?)?;
+            return *row.get(usize::try_from(x).ok()?)?;
+        };
+
+        let mut graph = Self::default();
+
+        for (y, row) in cells.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                if cell.is_some() {
+                    let node = node_at(x, y)?;
+
+                    graph.add_node(node);
+                    graph.set_coordinates(node, Coordinates::new(x as f64, y as f64));
+                }
+            }
+        }
+
+        let directions: &[(isize, isize)] = match options.connectivity {
+            Connectivity::Four => &[(1, 0), (-1, 0), (0, 1), (0, -1)],
+            Connectivity::Eight => &[(1, 0), (-1, 0), (0, 1), (0, -1), (1, 1), (1, -1), (-1, 1), (-1, -1)],
+        };
+
+        for (y, row) in cells.iter().enumerate() {
+            for x in (0..row.len()).filter(|&x| row[x].is_some()) {
+                let (column, line) = (x as isize, y as isize);
+
+                for &(dx, dy) in directions {
+                    let Some(cost) = cost_at(column + dx, line + dy) else {
+                        continue;
+                    };
+
+                    let is_diagonal = dx != 0 && dy != 0;
+                    if is_diagonal && !options.cut_corners && (cost_at(column + dx, line).is_none() || cost_at(column, line + dy).is_none()) {
+                        continue;
+                    }
+
+                    let weight = if is_diagonal { E::from_distance((cost.to_distance() * options.diagonal_factor).ceil()) } else { cost };
+
+                    graph.add_edge_directed(node_at(x, y)?, node_at((column + dx) as usize, (line + dy) as usize)?, weight);
+                }
+            }
+        }
+
+        return Ok(graph);
+    }
+
+    /// Builds a graph from a 2D passability map, where `true` is a passable cell and a straight move costs `cost`. See `from_grid()`.
+    pub fn from_passability_grid(cells: &[Vec<bool>], cost: E, options: &GridOptions) -> Result<Self, GraphError<N>> {
+        let cells: Vec<Vec<Option<E>>> = cells.iter().map(|row| row.iter().map(|&passable| passable.then_some(cost)).collect()).collect();
+
+        return Self::from_grid(&cells, options);
+    }
+}
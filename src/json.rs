@@ -0,0 +1,67 @@
+use crate::serde_impls::SerializedGraph;
+use crate::{Graph, ParseError};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::io;
+use std::io::Read;
+use std::io::Write;
+
+impl<N: Copy + Eq + Hash, E: Copy + PartialEq> Graph<N, E> {
+    /// Serializes a graph into JSON format. (uses serde)
+    /// Writes an object with the adjacency map in `nodes` and node coordinates in `coordinates`, which is left out if no node has coordinates.
+    /// Returns an error if nodes can't be JSON object keys, e.g. tuples. Numbers and strings work.
+    pub fn serialize(&self) -> Result<String, serde_json::Error>
+    where
+        N: Serialize,
+        E: Serialize,
+    {
+        return serde_json::to_string(self);
+    }
+
+    /// Serializes a graph into JSON format and writes it to `writer`. Produces the same output as `serialize()` and fails in the same cases.
+    pub fn serialize_to_writer<W: Write>(&self, writer: W) -> io::Result<()>
+    where
+        N: Serialize,
+        E: Serialize,
+    {
+        serde_json::to_writer(writer, self)?;
+
+        return Ok(());
+    }
+
+    /// Deserializes a graph from JSON produced by `serialize()`, or from a plain adjacency map written by earlier versions.
+    /// Returns `ParseError` if the input is malformed or if an edge or coordinates point to a node which is not present in the graph.
+    pub fn deserialize(json: &str) -> Result<Self, ParseError>
+    where
+        N: DeserializeOwned + Debug,
+        E: DeserializeOwned,
+    {
+        return Self::from_json(serde_json::from_str(json)?);
+    }
+
+    /// Deserializes a graph from JSON produced by `serialize()` or `serialize_to_writer()`, reading it from `reader`.
+    pub fn deserialize_from_reader<R: Read>(reader: R) -> Result<Self, ParseError>
+    where
+        N: DeserializeOwned + Debug,
+        E: DeserializeOwned,
+    {
+        return Self::from_json(serde_json::from_reader(reader)?);
+    }
+
+    /// Builds a graph from parsed JSON, which is either an object with `nodes` and optional `coordinates` or a plain adjacency map written by earlier versions.
+    fn from_json(json: Value) -> Result<Self, ParseError>
+    where
+        N: DeserializeOwned + Debug,
+        E: DeserializeOwned,
+    {
+        if json.get("nodes").is_none() {
+            return Self::from_serialized(SerializedGraph { nodes: serde_json::from_value(json)?, coordinates: HashMap::new() });
+        }
+
+        return Self::from_serialized(serde_json::from_value(json)?);
+    }
+}
@@ -0,0 +1,82 @@
+use crate::path::path_from_parents;
+use crate::{compare_weights, Graph, GraphError, Path, Weight};
+use std::collections::HashSet;
+use std::hash::Hash;
+
+impl<N: Copy + Eq + Hash, E: Weight> Graph<N, E> {
+    /// Finds up to `k` shortest loopless paths from `source` to `target` using Yen algorithm, in the order of increasing cost.
+    /// The first path is the one `shortest_path()` finds, every next one is the cheapest path which differs from all previous ones.
+    /// Returns fewer than `k` paths if there are no more, and no paths if `target` can't be reached.
+    pub fn k_shortest_paths(&self, source: N, target: N, k: usize) -> Vec<Path<N, E>> {
+        let (_, parents) = self.dijkstra(source, Some(target));
+
+        let Some(shortest) = path_from_parents(source, target, &parents).filter(|_| k > 0) else {
+            return Vec::new();
+        };
+
+        let mut paths: Vec<Path<N, E>> = vec![shortest];
+        let mut candidates: Vec<Path<N, E>> = Vec::new();
+        let mut seen: HashSet<Vec<N>> = HashSet::from([paths[0].nodes.clone()]);
+
+        while paths.len() < k {
+            let previous = paths.last().unwrap();
+
+            // deviate from the previous path at every node, keeping the part before it (the root) unchanged
+            for spur_index in 0..previous.nodes.len() - 1 {
+                let spur_node = previous.nodes[spur_index];
+                let root = &previous.nodes[..=spur_index];
+
+                // edges by which the already found paths with the same root leave the spur node, and nodes of the root, are hidden
+                let hidden_edges: HashSet<(N, N)> = paths.iter()
+                    .filter(|path| path.nodes.len() > spur_index + 1 && &path.nodes[..=spur_index] == root)
+                    .map(|path| (spur_node, path.nodes[spur_index + 1]))
+                    .collect();
+                let hidden_nodes: HashSet<N> = root[..spur_index].iter().copied().collect();
+
+                let (_, spur_parents) = self.dijkstra_filtered(spur_node, Some(target), |edge_source, edge_target, _| {
+                    return !hidden_nodes.contains(&edge_target) && !hidden_edges.contains(&(edge_source, edge_target));
+                });
+
+                let Some(spur_path) = path_from_parents(spur_node, target, &spur_parents) else {
+                    continue;
+                };
+
+                let mut nodes = root.to_vec();
+                nodes.extend_from_slice(&spur_path.nodes[1..]);
+
+                if seen.insert(nodes.clone()) {
+                    let mut weights = previous.weights[..spur_index].to_vec();
+                    weights.extend_from_slice(&spur_path.weights);
+                    let Some(cost) = weights.iter().try_fold(E::zero(), |cost, &weight| cost.checked_add(weight)) else {
+                        continue;
+                    };
+
+                    candidates.push(Path { nodes, weights, cost });
+                }
+            }
+
+            let Some(cheapest) = (0..candidates.len()).min_by(|&a, &b| compare_weights(&candidates[a].cost, &candidates[b].cost)) else {
+                break;
+            };
+
+            paths.push(candidates.swap_remove(cheapest));
+        }
+
+        return paths;
+    }
+
+    /// Finds up to `k` shortest loopless paths from `source` to `target` like `k_shortest_paths()`.
+    /// Returns `GraphError::UnknownNode` if `source` or `target` is not present in the graph and `GraphError::NoPath` if `target` can't be reached.
+    pub fn try_k_shortest_paths(&self, source: N, target: N, k: usize) -> Result<Vec<Path<N, E>>, GraphError<N>> {
+        self.check_node(source)?;
+        self.check_node(target)?;
+
+        let paths = self.k_shortest_paths(source, target, k);
+
+        if paths.is_empty() && k > 0 {
+            return Err(GraphError::NoPath { source, target });
+        }
+
+        return Ok(paths);
+    }
+}
@@ -1,3 +1,5 @@
+#![allow(clippy::needless_return, clippy::bool_assert_comparison, clippy::unnecessary_cast)]
+
 fn main() {}
 
 #[cfg(test)]
@@ -21,6 +23,10 @@ mod tests {
         return graph;
     }
 
+    fn edge_sets(graph: &mgraph::Graph) -> HashMap<u32, HashSet<(u32, i32)>> {
+        return graph.nodes.iter().map(|(&node, adjacents)| (node, adjacents.iter().copied().collect())).collect();
+    }
+
     #[test]
     fn test_graph_add_edge() {
         let graph = generate_test_graph();
@@ -120,7 +126,7 @@ mod tests {
     fn test_breadth_first_search() {
         let graph = generate_test_graph();
 
-        assert_eq!(graph.breadth_first_search(0, 2), 2)
+        assert_eq!(graph.breadth_first_search(0, 2), Some(2))
     }
 
     #[test]
@@ -197,13 +203,16 @@ mod tests {
         graph.add_edge(1, 2, 7);
         graph.add_edge(2, 3, 8);
 
-        fn heuristic(_node: u32, _target: u32) -> u32 {
+        fn heuristic(_node: u32, _target: u32) -> i32 {
             return 1;
         }
 
         let result = graph.astar(0, 2, heuristic);
 
-        assert_eq!(result, 11);
+        assert_eq!(result.cost, Some(11));
+        assert_eq!(result.path.unwrap().nodes, vec![0, 2]);
+        assert_eq!(graph.astar(0, 3, heuristic), graph.shortest_path(0, 3));
+        assert_eq!(graph.astar(2, 2, heuristic).path.unwrap().nodes, vec![2]);
     }
     #[test]
     fn test_euclidian_distance() {
@@ -231,10 +240,11 @@ mod tests {
         graph.add_edge(1, 2, 7);
         graph.add_edge(2, 3, 8);
 
-        let distances = graph.bellman_ford(0);
-        let result = distances.get(&2).unwrap();
+        let result = graph.bellman_ford(0);
 
-        assert_eq!(*result, 11);
+        assert_eq!(result.distance(2), Some(11));
+        assert_eq!(result.path_to(3).unwrap().nodes, vec![0, 2, 3]);
+        assert_eq!(result.negative_cycle, None);
     }
 
     #[test]
@@ -251,10 +261,16 @@ mod tests {
         graph.add_edge(2, 3, 1); 
         graph.add_edge(3, 0, -4);
 
-        let distances = graph.bellman_ford(0);
-        let result = graph.has_negative_cycle(&distances);
+        let result = graph.bellman_ford(0);
+
+        assert_eq!(graph.has_negative_cycle(), true);
+        assert_eq!(result.distance(1), None);
 
-        assert_eq!(result, true);
+        let cycle = result.negative_cycle.unwrap();
+        let weight: i32 = cycle.iter().zip(cycle.iter().cycle().skip(1)).map(|(&node, &next)| {
+            graph.get_node_adjacents(node).iter().filter(|&&(adjacent, _)| adjacent == next).map(|&(_, weight)| weight).min().unwrap()
+        }).sum();
+        assert!(weight < 0);
     }
 
     #[test]
@@ -271,9 +287,1295 @@ mod tests {
         graph.add_edge(2, 3, 4); 
         graph.add_edge(3, 0, 1);
 
-        let distances = graph.bellman_ford(0);
-        let result = graph.has_negative_cycle(&distances);
+        let result = graph.bellman_ford(0);
+
+        assert_eq!(graph.has_negative_cycle(), false);
+        assert_eq!(graph.find_negative_cycle(), None);
+        assert_eq!(result.negative_cycle, None);
+        assert_eq!(result.distance(3), Some(1));
+    }
+
+    #[test]
+    fn test_graph_weight_overflow() {
+        let mut graph = mgraph::Graph::new();
+
+        for node in 0..3 {
+            graph.add_node(node);
+        }
+
+        graph.add_edge_directed(0, 1, i32::MAX);
+        graph.add_edge_directed(1, 2, 1);
+
+        assert_eq!(graph.shortest_path(0, 1).cost, Some(i32::MAX));
+        assert_eq!(graph.shortest_path(0, 2).cost, None);
+        assert_eq!(graph.bidirectional_shortest_path(0, 2).cost, None);
+        assert_eq!(graph.astar(0, 2, |_, _| 0).cost, None);
+        assert_eq!(graph.contraction_hierarchy().unwrap().shortest_path(0, 2).cost, None);
+        assert_eq!(graph.longest_path(0, 2), Ok(None));
+        assert_eq!(graph.floyd_warshall().unwrap().distance(0, 2), None);
+
+        let result = graph.bellman_ford(0);
+        assert_eq!(result.distance(1), Some(i32::MAX));
+        assert_eq!(result.distance(2), None);
+        assert_eq!(result.negative_cycle, None);
+    }
+
+    #[test]
+    fn test_graph_generic_nodes_and_weights() {
+        let mut graph: mgraph::Graph<&str, u64> = mgraph::Graph::default();
+
+        graph.add_node("a");
+        graph.add_node("b");
+        graph.add_node("c");
+
+        graph.add_edge("a", "b", 3);
+        graph.add_edge("b", "c", 4);
+        graph.add_edge_directed("a", "c", 10);
+
+        let result = graph.shortest_path("a", "c");
+
+        assert_eq!(result.cost, Some(7));
+        assert_eq!(graph.restore_path("a", "c", result.parents.unwrap()), ["a", "b", "c"]);
+        assert_eq!(graph.depth_first_search("c"), HashSet::from(["a", "b", "c"]));
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_graph_deserialize_round_trip() {
+        let mut graph = generate_test_graph();
+        graph.add_node(3);
+        graph.add_edge_directed(2, 3, -5);
+
+        let restored: mgraph::Graph = mgraph::Graph::deserialize(&graph.serialize().unwrap()).unwrap();
+        assert_eq!(edge_sets(&restored), edge_sets(&graph));
+
+        let mut buffer: Vec<u8> = Vec::new();
+        graph.serialize_to_writer(&mut buffer).unwrap();
+
+        let restored: mgraph::Graph = mgraph::Graph::deserialize_from_reader(buffer.as_slice()).unwrap();
+        assert_eq!(edge_sets(&restored), edge_sets(&graph));
+
+        let mut grid: mgraph::Graph<(u32, u32), i32> = mgraph::Graph::default();
+        grid.add_node((0, 0));
+        grid.add_node((0, 1));
+        grid.add_edge((0, 0), (0, 1), 1);
+
+        assert!(grid.serialize().is_err());
+        assert!(grid.serialize_to_writer(Vec::new()).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_graph_coordinates_round_trip() {
+        let mut graph = generate_test_graph();
+        graph.set_coordinates(0, mgraph::Coordinates::new(1.5, -2.0));
+        graph.set_coordinates(2, mgraph::Coordinates::new_3d(0.0, 3.0, 4.25));
+
+        let json = graph.serialize().unwrap();
+        assert!(json.contains(r#""coordinates""#));
+
+        let restored: mgraph::Graph = mgraph::Graph::deserialize(&json).unwrap();
+        assert_eq!(edge_sets(&restored), edge_sets(&graph));
+        assert_eq!(restored.coordinates, graph.coordinates);
+
+        let restored: mgraph::Graph = serde_json::from_str(&serde_json::to_string(&graph).unwrap()).unwrap();
+        assert_eq!(restored.coordinates, graph.coordinates);
+
+        let unknown: Result<mgraph::Graph, _> = mgraph::Graph::deserialize(r#"{"nodes":{"0":[]},"coordinates":{"1":{"x":0.0,"y":0.0,"z":0.0}}}"#);
+        assert!(matches!(unknown, Err(mgraph::ParseError::Invalid(_))));
+
+        let plain = generate_test_graph();
+        assert!(!plain.serialize().unwrap().contains("coordinates"));
+
+        let legacy: mgraph::Graph = mgraph::Graph::deserialize(r#"{"0":[[1,6]],"1":[[0,6]]}"#).unwrap();
+        assert_eq!(legacy.get_node_adjacents(0), &vec![(1, 6)]);
+        assert!(legacy.coordinates.is_empty());
+
+        let empty: mgraph::Graph = mgraph::Graph::deserialize("{}").unwrap();
+        assert!(empty.nodes.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "graphml")]
+    fn test_graph_graphml_coordinates() {
+        let mut graph = generate_test_graph();
+        graph.set_coordinates(0, mgraph::Coordinates::new(1.5, -2.0));
+        graph.set_coordinates(2, mgraph::Coordinates::new_3d(0.0, 3.0, 4.25));
+
+        let graphml = graph.to_graphml();
+        assert!(graphml.contains(r#"<key id="x" for="node" attr.name="x" attr.type="double"/>"#));
+
+        let restored: mgraph::Graph = mgraph::Graph::from_graphml(&graphml).unwrap();
+        assert_eq!(edge_sets(&restored), edge_sets(&graph));
+        assert_eq!(restored.coordinates, graph.coordinates);
+
+        assert!(!generate_test_graph().to_graphml().contains(r#"for="node""#));
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_graph_deserialize_invalid() {
+        let dangling: Result<mgraph::Graph, _> = mgraph::Graph::deserialize(r#"{"0":[[1,4]]}"#);
+        assert!(matches!(dangling, Err(mgraph::ParseError::DanglingEdge { .. })));
+
+        let malformed: Result<mgraph::Graph, _> = mgraph::Graph::deserialize(r#"{"0":[[1,"#);
+        assert!(matches!(malformed, Err(mgraph::ParseError::Json(_))));
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_graph_serde_impls() {
+        let graph = generate_test_graph();
+
+        assert_eq!(serde_json::to_string(&graph).unwrap(), graph.serialize().unwrap());
+
+        let restored: mgraph::Graph = serde_json::from_str(&graph.serialize().unwrap()).unwrap();
+        assert_eq!(edge_sets(&restored), edge_sets(&graph));
+
+        let dangling: Result<mgraph::Graph, _> = serde_json::from_str(r#"{"0":[[1,4]]}"#);
+        assert!(dangling.is_err());
+
+        let result = graph.shortest_path(0, 2);
+        let restored: mgraph::DijkstraResult = serde_json::from_str(&serde_json::to_string(&result).unwrap()).unwrap();
+        assert_eq!(restored, result);
+    }
+
+    #[test]
+    #[cfg(feature = "graphml")]
+    fn test_graph_graphml_round_trip() {
+        let mut graph = generate_test_graph();
+        graph.add_node(3);
+        graph.add_edge_directed(2, 3, 5);
+
+        let restored: mgraph::Graph = mgraph::Graph::from_graphml(&graph.to_graphml()).unwrap();
+
+        assert_eq!(edge_sets(&restored), edge_sets(&graph));
+        assert_eq!(restored.has_edge(1, 2), true);
+        assert_eq!(restored.has_edge_directed(3, 2), false);
+    }
+
+    #[test]
+    #[cfg(feature = "graphml")]
+    fn test_graph_from_graphml() {
+        let graphml = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <graphml xmlns="http://graphml.graphdrawing.org/xmlns" xmlns:y="http://www.yworks.com/xml/graphml">
+              <key id="d0" for="edge" attr.name="weight" attr.type="int"><default>1</default></key>
+              <key id="d1" for="node" yfiles.type="nodegraphics"/>
+              <graph id="G" edgedefault="undirected">
+                <node id="0"><data key="d1"><y:ShapeNode/></data></node>
+                <node id="1"/>
+                <node id="2"/>
+                <edge source="0" target="1"><data key="d0">4</data></edge>
+                <edge source="1" target="2" directed="true"/>
+              </graph>
+            </graphml>"#;
+
+        let graph: mgraph::Graph = mgraph::Graph::from_graphml(graphml).unwrap();
+
+        assert_eq!(graph.get_node_adjacents(1).iter().copied().collect::<HashSet<_>>(), HashSet::from([(0, 4), (2, 1)]));
+        assert_eq!(graph.has_edge(0, 1), true);
+        assert_eq!(graph.has_edge_directed(2, 1), false);
+
+        let dangling: Result<mgraph::Graph, _> = mgraph::Graph::from_graphml(&graphml.replace(r#"target="2""#, r#"target="7""#));
+        assert!(matches!(dangling, Err(mgraph::ParseError::DanglingEdge { .. })));
+
+        let malformed: Result<mgraph::Graph, _> = mgraph::Graph::from_graphml("<graphml><graph></graphml>");
+        assert!(matches!(malformed, Err(mgraph::ParseError::Xml(_))));
+
+        let gephi = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+              <key attr.name="label" attr.type="string" for="node" id="label"/>
+              <key attr.name="weight" attr.type="double" for="edge" id="weight"/>
+              <graph edgedefault="directed">
+                <node id="0"><data key="label">A</data></node>
+                <node id="1"><data key="label">B</data></node>
+                <edge id="0" source="0" target="1"><data key="weight">1.0</data></edge>
+                <edge id="1" source="1" target="0"><data key="weight">2.5</data></edge>
+              </graph>
+            </graphml>"#;
+
+        let invalid: Result<mgraph::Graph, _> = mgraph::Graph::from_graphml(gephi);
+        assert!(matches!(invalid, Err(mgraph::ParseError::Invalid(_))));
+
+        let graph: mgraph::Graph = mgraph::Graph::from_graphml(&gephi.replace("2.5", "3.0")).unwrap();
+        assert_eq!(graph.get_node_adjacents(0).iter().copied().collect::<HashSet<_>>(), HashSet::from([(1, 1)]));
+        assert_eq!(graph.get_node_adjacents(1).iter().copied().collect::<HashSet<_>>(), HashSet::from([(0, 3)]));
+        assert!(graph.to_graphml().contains(r#"attr.type="long""#));
+    }
+
+    #[test]
+    #[cfg(feature = "graphml")]
+    fn test_graph_from_graphml_yed() {
+        let graphml = r#"<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+            <graphml xmlns="http://graphml.graphdrawing.org/xmlns" xmlns:java="http://www.yworks.com/xml/yfiles-common/1.0/java" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xmlns:y="http://www.yworks.com/xml/graphml" xsi:schemaLocation="http://graphml.graphdrawing.org/xmlns http://www.yworks.com/xml/schema/graphml/1.1/ygraphml.xsd">
+              <key for="graphml" id="d0" yfiles.type="resources"/>
+              <key attr.name="url" attr.type="string" for="node" id="d4"/>
+              <key for="node" id="d6" yfiles.type="nodegraphics"/>
+              <key attr.name="weight" attr.type="double" for="edge" id="d9"/>
+              <key for="edge" id="d10" yfiles.type="edgegraphics"/>
+              <graph edgedefault="directed" id="G">
+                <node id="n0">
+                  <data key="d6">
+                    <y:ShapeNode>
+                      <y:Geometry height="30.0" width="30.0" x="100.0" y="50.0"/>
+                      <y:NodeLabel>Kyiv</y:NodeLabel>
+                    </y:ShapeNode>
+                  </data>
+                </node>
+                <node id="n1">
+                  <data key="d6"><y:ShapeNode><y:NodeLabel>Lviv</y:NodeLabel></y:ShapeNode></data>
+                </node>
+                <node id="n2"/>
+                <edge id="e0" source="n0" target="n1">
+                  <data key="d9">540.0</data>
+                  <data key="d10">
+                    <y:PolyLineEdge>
+                      <y:Arrows source="none" target="standard"/>
+                      <y:EdgeLabel>540 km</y:EdgeLabel>
+                    </y:PolyLineEdge>
+                  </data>
+                </edge>
+                <edge id="e1" source="n1" target="n2">
+                  <data key="d10"><y:PolyLineEdge><y:Arrows source="none" target="standard"/></y:PolyLineEdge></data>
+                </edge>
+              </graph>
+              <data key="d0"><y:Resources/></data>
+            </graphml>"#;
+
+        let numeric: Result<mgraph::Graph, _> = mgraph::Graph::from_graphml(graphml);
+        assert!(matches!(numeric, Err(mgraph::ParseError::Invalid(_))));
+
+        let (graph, ids): (mgraph::Graph<usize, u32>, _) = mgraph::Graph::from_graphml_with_ids(graphml, 1).unwrap();
+
+        assert_eq!(ids.len(), 3);
+        assert_eq!(ids.get(0).map(String::as_str), Some("n0"));
+
+        let kyiv = ids.id(&String::from("n0")).unwrap();
+        let lviv = ids.id(&String::from("n1")).unwrap();
+        let odesa = ids.id(&String::from("n2")).unwrap();
+
+        assert_eq!(graph.get_node_adjacents(kyiv), &vec![(lviv, 540)]);
+        assert_eq!(graph.get_node_adjacents(lviv), &vec![(odesa, 1)]);
+        assert_eq!(graph.shortest_path(kyiv, odesa).cost, Some(541));
+
+        let unweighted = graphml.replace("<data key=\"d9\">540.0</data>", "");
+        let graph: mgraph::Graph<usize, u32> = mgraph::Graph::from_graphml_with_ids(&unweighted, 1).unwrap().0;
+        assert_eq!(graph.shortest_path(kyiv, odesa).cost, Some(2));
+
+        let graph: mgraph::Graph = mgraph::Graph::from_graphml(&unweighted.replace("\"n", "\"")).unwrap();
+        assert_eq!(graph.get_node_adjacents(0), &vec![(1, 0)]);
+
+        let graph: mgraph::Graph = mgraph::Graph::from_graphml_with_default_weight(&unweighted.replace("\"n", "\""), 7).unwrap();
+        assert_eq!(graph.get_node_adjacents(1), &vec![(2, 7)]);
+    }
+
+    #[test]
+    fn test_graph_dot_round_trip() {
+        let mut graph = generate_test_graph();
+
+        assert!(graph.to_dot().starts_with("graph {"));
+
+        let restored: mgraph::Graph = mgraph::Graph::from_dot(&graph.to_dot()).unwrap();
+        assert_eq!(edge_sets(&restored), edge_sets(&graph));
+
+        graph.add_node(3);
+        graph.add_edge_directed(2, 3, 5);
+
+        let dot = graph.to_dot_with_path([0, 1, 2]);
+        assert!(dot.starts_with("digraph {"));
+        assert!(dot.contains(r#""0" [color=red, penwidth=2];"#));
+        assert!(dot.contains(r#""2" -> "3" [label="5"];"#));
+
+        let restored: mgraph::Graph = mgraph::Graph::from_dot(&dot).unwrap();
+        assert_eq!(edge_sets(&restored), edge_sets(&graph));
+    }
+
+    #[test]
+    fn test_graph_from_dot() {
+        let dot = r#"
+            // routing test
+            digraph G {
+                node [shape=circle];
+                rankdir = LR;
+                0 -> 1 -> 2 [weight=3];
+                2 -> 0 [label="7", dir=both]
+                /* isolated */
+                "3";
+            }
+        "#;
+
+        let graph: mgraph::Graph = mgraph::Graph::from_dot(dot).unwrap();
+
+        assert_eq!(graph.nodes.len(), 4);
+        assert_eq!(graph.get_node_adjacents(1).iter().copied().collect::<HashSet<_>>(), HashSet::from([(2, 3)]));
+        assert_eq!(graph.has_edge(0, 2), true);
+        assert_eq!(graph.has_edge_directed(1, 0), false);
+
+        let error: Result<mgraph::Graph, _> = mgraph::Graph::from_dot("graph {\n 0 -> 1 [label=1];\n}");
+        assert!(matches!(error, Err(mgraph::ParseError::Syntax { line: 2, .. })));
+    }
+
+    #[test]
+    fn test_graph_from_dot_default_weights() {
+        let graph: mgraph::Graph = mgraph::Graph::from_dot("digraph { 0 -> 1; 1 -> 2 }").unwrap();
+
+        assert_eq!(graph.get_node_adjacents(0), &vec![(1, 0)]);
+        assert_eq!(graph.get_node_adjacents(1), &vec![(2, 0)]);
+
+        let graph: mgraph::Graph = mgraph::Graph::from_dot_with_default_weight("digraph { 0 -> 1; 1 -> 2 }", 1).unwrap();
+        assert_eq!(graph.shortest_path(0, 2).cost, Some(2));
+
+        let dot = "digraph {\n 0 -> 1;\n edge [weight=3];\n 1 -> 2;\n 2 -> 3 [label=5];\n edge [dir=both];\n 3 -> 4;\n}";
+        let graph: mgraph::Graph = mgraph::Graph::from_dot_with_default_weight(dot, 1).unwrap();
+
+        assert_eq!(graph.get_node_adjacents(0), &vec![(1, 1)]);
+        assert_eq!(graph.get_node_adjacents(1), &vec![(2, 3)]);
+        assert_eq!(graph.get_node_adjacents(2), &vec![(3, 3)]);
+        assert_eq!(graph.get_node_adjacents(4), &vec![(3, 3)]);
+    }
+
+    #[test]
+    fn test_graph_dot_escaping() {
+        let mut graph: mgraph::Graph<char, i32> = mgraph::Graph::default();
+
+        graph.add_node('\\');
+        graph.add_node('"');
+        graph.add_edge_directed('\\', '"', 1);
+
+        let dot = graph.to_dot();
+        assert!(dot.contains(r#""\\" -> "\"" [label="1"];"#));
+
+        let restored: mgraph::Graph<char, i32> = mgraph::Graph::from_dot(&dot).unwrap();
+        assert_eq!(restored.nodes, graph.nodes);
+    }
+
+    #[test]
+    fn test_graph_from_edge_list() {
+        let edge_list = "# Directed graph: test.txt\n# FromNodeId\tToNodeId\n0\t1\n1\t2\t5\n\n3\n";
+
+        let options = mgraph::EdgeListOptions { directed: false, default_weight: Some(1), ..Default::default() };
+        let graph: mgraph::Graph = mgraph::Graph::from_edge_list(edge_list, &options).unwrap();
+
+        assert_eq!(graph.nodes.len(), 4);
+        assert_eq!(graph.get_node_adjacents(1).iter().copied().collect::<HashSet<_>>(), HashSet::from([(0, 1), (2, 5)]));
+
+        let error: Result<mgraph::Graph, _> = mgraph::Graph::from_edge_list(edge_list, &mgraph::EdgeListOptions::default());
+        assert!(matches!(error, Err(mgraph::ParseError::Syntax { line: 3, .. })));
+
+        let mut graph = generate_test_graph();
+        graph.add_node(3);
+        graph.add_edge_directed(2, 1, 4);
+
+        let options = mgraph::EdgeListOptions::default();
+        let restored: mgraph::Graph = mgraph::Graph::from_edge_list(&graph.to_edge_list(&options), &options).unwrap();
+        assert_eq!(edge_sets(&restored), edge_sets(&graph));
+    }
+
+    #[test]
+    fn test_graph_dimacs_and_matrix_market() {
+        let dimacs = "c road network\np sp 3 2\na 1 2 7\na 2 3 4\n";
+
+        let graph: mgraph::Graph = mgraph::Graph::from_dimacs(dimacs, &mgraph::EdgeListOptions::default()).unwrap();
+        assert_eq!(graph.shortest_path(0, 2).cost, Some(11));
+        assert_eq!(graph.has_edge_directed(1, 0), false);
+
+        let restored: mgraph::Graph = mgraph::Graph::from_dimacs(&graph.to_dimacs().unwrap(), &mgraph::EdgeListOptions::default()).unwrap();
+        assert_eq!(edge_sets(&restored), edge_sets(&graph));
+
+        let matrix_market = "%%MatrixMarket matrix coordinate pattern symmetric\n% comment\n3 3 2\n2 1\n3 2\n";
+        let options = mgraph::EdgeListOptions { default_weight: Some(1), ..Default::default() };
+
+        let graph: mgraph::Graph = mgraph::Graph::from_matrix_market(matrix_market, &options).unwrap();
+        assert_eq!(graph.get_node_adjacents(1).iter().copied().collect::<HashSet<_>>(), HashSet::from([(0, 1), (2, 1)]));
+        assert_eq!(graph.has_edge(1, 2), true);
+
+        let graph = generate_test_graph();
+        let restored: mgraph::Graph = mgraph::Graph::from_matrix_market(&graph.to_matrix_market().unwrap(), &options).unwrap();
+        assert_eq!(edge_sets(&restored), edge_sets(&graph));
+    }
+
+    #[test]
+    fn test_graph_adjacency_matrix() {
+        let graph = generate_test_graph();
+        let matrix = graph.to_adjacency_matrix().unwrap();
+
+        assert_eq!(matrix, "0 1 0\n1 0 2\n0 2 0\n");
+
+        let restored: mgraph::Graph = mgraph::Graph::from_adjacency_matrix(&matrix, &mgraph::EdgeListOptions::default()).unwrap();
+        assert_eq!(edge_sets(&restored), edge_sets(&graph));
+
+        let mut graph = generate_test_graph();
+        graph.add_edge_directed(0, 2, 0);
+
+        let error = graph.to_adjacency_matrix().unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+
+        let padded: mgraph::Graph = mgraph::Graph::from_adjacency_matrix("00 1 -0\n+1 0 2\n0 02 0\n", &mgraph::EdgeListOptions::default()).unwrap();
+        assert_eq!(edge_sets(&padded), edge_sets(&generate_test_graph()));
+
+        let mut floats: mgraph::Graph<u32, f64> = mgraph::Graph::from_adjacency_matrix("0.0 1.5\n-0.0 0\n", &mgraph::EdgeListOptions::default()).unwrap();
+        assert_eq!(floats.get_node_adjacents(0), &vec![(1, 1.5)]);
+        assert!(floats.get_node_adjacents(1).is_empty());
+        assert_eq!(floats.to_adjacency_matrix().unwrap(), "0 1.5\n0 0\n");
+
+        floats.add_edge_directed(1, 0, -0.0);
+        assert!(floats.to_adjacency_matrix().is_err());
+    }
+
+    #[test]
+    fn test_graph_try_functions() {
+        let mut graph = generate_test_graph();
+        graph.add_node(3);
+
+        assert_eq!(graph.try_add_edge_directed(0, 7, 1), Err(mgraph::GraphError::UnknownNode(7)));
+        assert!(matches!(graph.try_add_edge(1, 1, 1), Err(mgraph::GraphError::InvalidInput(_))));
+        assert_eq!(graph.try_get_node_adjacents(7), Err(mgraph::GraphError::UnknownNode(7)));
+        assert_eq!(graph.try_depth_first_search(7), Err(mgraph::GraphError::UnknownNode(7)));
+
+        assert_eq!(graph.try_shortest_path(0, 3), Err(mgraph::GraphError::NoPath { source: 0, target: 3 }));
+        assert_eq!(graph.try_shortest_path(0, 2).unwrap().cost, Some(3));
+        assert_eq!(graph.try_astar(0, 3, |_, _| 0), Err(mgraph::GraphError::NoPath { source: 0, target: 3 }));
+        assert_eq!(graph.try_restore_path(0, 3, &HashMap::from([(2, 1)])), Err(mgraph::GraphError::NoPath { source: 0, target: 3 }));
+
+        graph.try_add_edge_directed(2, 3, -4).unwrap();
+        graph.try_add_edge_directed(3, 1, 1).unwrap();
+
+        assert!(matches!(graph.try_bellman_ford(0), Err(mgraph::GraphError::NegativeCycle(_))));
+        assert_eq!(graph.bellman_ford(0).negative_cycle.map(|cycle| cycle.len()), Some(3));
+        assert_eq!(graph.try_bellman_ford(7), Err(mgraph::GraphError::UnknownNode(7)));
+    }
+
+    #[test]
+    fn test_graph_shortest_path_returns_path() {
+        let mut graph = mgraph::Graph::new();
+
+        graph.add_node(0);
+        graph.add_node(1);
+        graph.add_node(2);
+        graph.add_node(3);
+
+        graph.add_edge(0, 1, 6);
+        graph.add_edge(0, 2, 16);
+        graph.add_edge(1, 2, 7);
+        graph.add_edge(2, 3, 8);
+
+        let path = graph.shortest_path(0, 3).path.unwrap();
+
+        assert_eq!(path.nodes, vec![0, 1, 2, 3]);
+        assert_eq!(path.weights, vec![6, 7, 8]);
+        assert_eq!(path.cost, 21);
+        assert_eq!((path.source(), path.target()), (0, 3));
+        assert_eq!(path.edges().collect::<Vec<_>>(), vec![(0, 1, 6), (1, 2, 7), (2, 3, 8)]);
+
+        assert_eq!(graph.shortest_path(2, 2).path.unwrap().nodes, vec![2]);
+        assert_eq!(graph.path_from_nodes(vec![0, 3]), None);
+    }
+
+    #[test]
+    fn test_graph_shortest_path_tree() {
+        let mut graph = mgraph::Graph::new();
+
+        graph.add_node(0);
+        graph.add_node(1);
+        graph.add_node(2);
+        graph.add_node(3);
+        graph.add_node(4);
+
+        graph.add_edge(0, 1, 6);
+        graph.add_edge(0, 2, 16);
+        graph.add_edge(1, 2, 7);
+        graph.add_edge_directed(2, 3, 8);
+
+        let tree = graph.shortest_path_tree(0);
+
+        assert_eq!(tree.distances, HashMap::from([(0, 0), (1, 6), (2, 13), (3, 21)]));
+        assert_eq!(tree.distance(4), None);
+        assert_eq!(tree.path_to(4), None);
+        assert_eq!(tree.path_to(0).unwrap().nodes, vec![0]);
+
+        for target in 0..4 {
+            assert_eq!(tree.path_to(target), graph.shortest_path(0, target).path);
+        }
+
+        assert!(graph.try_shortest_path_tree(7).is_err());
+    }
+
+    #[test]
+    fn test_graph_all_pairs_shortest_paths() {
+        let mut graph = mgraph::Graph::new();
+
+        graph.add_node(0);
+        graph.add_node(1);
+        graph.add_node(2);
+        graph.add_node(3);
+        graph.add_node(4);
+
+        graph.add_edge(0, 1, 6);
+        graph.add_edge(0, 2, 16);
+        graph.add_edge(1, 2, 7);
+        graph.add_edge_directed(2, 3, 8);
+        graph.add_edge_directed(3, 1, -2);
+
+        for all_pairs in [graph.floyd_warshall().unwrap(), graph.johnson().unwrap()] {
+            assert_eq!(all_pairs.distance(0, 3), Some(21));
+            assert_eq!(all_pairs.distance(3, 0), Some(4));
+            assert_eq!(all_pairs.distance(2, 2), Some(0));
+            assert_eq!(all_pairs.distance(0, 4), None);
+            assert_eq!(all_pairs.next_hop(0, 3), Some(1));
+            assert_eq!(all_pairs.next_hop(2, 2), None);
+            assert_eq!(all_pairs.path(4, 0), None);
+
+            let path = all_pairs.path(3, 0).unwrap();
+            assert_eq!(path.nodes, vec![3, 1, 0]);
+            assert_eq!(path.weights, vec![-2, 6]);
+            assert_eq!(path.cost, 4);
+
+            for target in 0..5 {
+                assert_eq!(all_pairs.path(0, target), graph.shortest_path_tree(0).path_to(target));
+            }
+        }
+
+        graph.add_edge_directed(1, 3, -7);
+
+        for error in [graph.floyd_warshall().unwrap_err(), graph.johnson().unwrap_err()] {
+            let mgraph::GraphError::NegativeCycle(mut cycle) = error else {
+                panic!("expected a negative cycle, got {:?}", error);
+            };
+
+            cycle.sort();
+            assert_eq!(cycle, vec![1, 3]);
+        }
+    }
+
+    #[test]
+    fn test_graph_astar_coordinates_heuristic() {
+        let mut graph = mgraph::Graph::new();
+
+        // 3x2 grid, node = y * 3 + x
+        for node in 0..6 {
+            graph.add_node(node);
+        }
+
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 2, 1);
+        graph.add_edge(3, 4, 1);
+        graph.add_edge(4, 5, 1);
+        graph.add_edge(0, 3, 1);
+        graph.add_edge(2, 5, 5);
+
+        let coordinates = |node: u32| (node % 3, node / 3);
+
+        for result in [graph.astar(0, 5, graph.manhattan_heuristic(coordinates)), graph.astar(0, 5, graph.euclidian_heuristic(coordinates))] {
+            assert_eq!(result.cost, Some(3));
+            assert_eq!(result.path.unwrap().nodes, vec![0, 3, 4, 5]);
+        }
+
+        assert_eq!(graph.euclidian_heuristic(coordinates)(0, 5), 2);
+
+        graph.drop_edge(4, 5);
+        graph.drop_edge(2, 5);
+
+        assert_eq!(graph.astar(0, 5, graph.manhattan_heuristic(coordinates)).cost, None);
+    }
+
+    #[test]
+    fn test_graph_coordinates_heuristics() {
+        use mgraph::{Coordinates, Heuristic};
+
+        let mut graph = mgraph::Graph::new();
+
+        for node in 0..4 {
+            graph.add_node(node);
+        }
+
+        graph.add_edge(0, 1, 5);
+        graph.add_edge(1, 2, 5);
+        graph.add_edge(0, 3, 4);
+        graph.add_edge(3, 2, 9);
+
+        graph.set_coordinates(0, Coordinates::new(0.0, 0.0));
+        graph.set_coordinates(1, Coordinates::new(3.0, 4.0));
+        graph.set_coordinates(2, Coordinates::new(6.0, 8.0));
+        graph.set_coordinates(7, Coordinates::new(1.0, 1.0));
+
+        assert_eq!(graph.get_coordinates(1), Some(Coordinates::new(3.0, 4.0)));
+        assert_eq!(graph.get_coordinates(3), None);
+        assert_eq!(graph.get_coordinates(7), None);
+        assert_eq!(graph.try_set_coordinates(7, Coordinates::default()), Err(mgraph::GraphError::UnknownNode(7)));
+
+        assert_eq!(graph.heuristic(Heuristic::Manhattan)(0, 1), 7);
+        assert_eq!(graph.heuristic(Heuristic::Euclidean)(0, 1), 5);
+        assert_eq!(graph.heuristic(Heuristic::Chebyshev)(0, 1), 4);
+        assert_eq!(graph.heuristic(Heuristic::Octile)(0, 1), 5);
+        assert_eq!(graph.scaled_heuristic(Heuristic::Euclidean, 10.0)(0, 1), 50);
+        assert_eq!(graph.heuristic(Heuristic::Euclidean)(0, 3), 0);
+
+        let positions = |node: u32| (node < 3).then(|| Coordinates::new(3.0 * node as f64, 4.0 * node as f64));
+        assert_eq!(graph.coordinate_heuristic(Heuristic::Euclidean, positions)(0, 2), 10);
+        assert_eq!(graph.coordinate_heuristic(Heuristic::Chebyshev, positions)(0, 1), 4);
+        assert_eq!(graph.coordinate_heuristic(Heuristic::Euclidean, positions)(0, 3), 0);
+        assert_eq!(graph.manhattan_heuristic(|node| (node, 2 * node))(0, 2), 6);
+
+        for heuristic in [Heuristic::Euclidean, Heuristic::Chebyshev, Heuristic::Octile] {
+            let result = graph.astar(0, 2, graph.heuristic(heuristic));
+
+            assert_eq!(result.cost, Some(10));
+            assert_eq!(result.path.unwrap().nodes, vec![0, 1, 2]);
+        }
+
+        let kyiv = Coordinates::from_lat_lon(50.4501, 30.5234);
+        let lviv = Coordinates::from_lat_lon(49.8397, 24.0297);
+        let distance = Heuristic::Haversine.distance(kyiv, lviv);
+        assert!((465_000.0..470_000.0).contains(&distance));
+
+        graph.drop_node(1);
+        assert_eq!(graph.get_coordinates(1), None);
+        assert_eq!(graph.remove_coordinates(2), Some(Coordinates::new(6.0, 8.0)));
+    }
+
+    #[test]
+    fn test_graph_from_grid() {
+        use mgraph::{Connectivity, GridOptions, Heuristic};
+
+        // . . .
+        // . # .
+        // . . 5
+        let cells = vec![
+            vec![Some(1), Some(1), Some(1)],
+            vec![Some(1), None, Some(1)],
+            vec![Some(1), Some(1), Some(5)],
+        ];
+
+        let graph: mgraph::Graph = mgraph::Graph::from_grid(&cells, &GridOptions::default()).unwrap();
+
+        assert_eq!(graph.nodes.len(), 8);
+        assert_eq!(graph.has_node(4), false);
+        assert_eq!(graph.get_coordinates(5), Some(mgraph::Coordinates::new(2.0, 1.0)));
+        assert_eq!(graph.has_edge_directed(0, 1), true);
+        assert_eq!(graph.has_edge_directed(0, 4), false);
+        assert_eq!(graph.get_node_adjacents(5).iter().copied().collect::<HashSet<_>>(), HashSet::from([(2, 1), (8, 5)]));
+
+        let result = graph.astar(0, 2, graph.heuristic(Heuristic::Manhattan));
+        assert_eq!(result.cost, Some(2));
+        assert_eq!(result.path.unwrap().nodes, vec![0, 1, 2]);
+
+        let result = graph.astar(0, 8, graph.manhattan_heuristic(|node| (node % 3, node / 3)));
+        assert_eq!(result.cost, Some(8));
+        assert_eq!(result, graph.shortest_path(0, 8));
+
+        let options = GridOptions { connectivity: Connectivity::Eight, ..GridOptions::default() };
+        let graph: mgraph::Graph = mgraph::Graph::from_passability_grid(&[vec![true, true], vec![true, true]], 10, &options).unwrap();
+
+        assert_eq!(graph.get_node_adjacents(1).iter().copied().collect::<HashSet<_>>(), HashSet::from([(0, 10), (3, 10), (2, 15)]));
+
+        let blocked = vec![vec![true, false], vec![false, true]];
+        let graph: mgraph::Graph = mgraph::Graph::from_passability_grid(&blocked, 10, &options).unwrap();
+        assert_eq!(graph.has_edge_directed(0, 3), false);
+
+        let options = GridOptions { cut_corners: true, ..options };
+        let graph: mgraph::Graph = mgraph::Graph::from_passability_grid(&blocked, 10, &options).unwrap();
+        assert_eq!(graph.astar(0, 3, graph.scaled_heuristic(Heuristic::Octile, 10.0)).cost, Some(15));
+
+        let ragged: Result<mgraph::Graph, _> = mgraph::Graph::from_grid(&[vec![Some(1)], vec![]], &options);
+        assert!(matches!(ragged, Err(mgraph::GraphError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_graph_bidirectional_shortest_path() {
+        let mut graph = mgraph::Graph::new();
+
+        for node in 0..7 {
+            graph.add_node(node);
+        }
+
+        graph.add_edge(0, 1, 6);
+        graph.add_edge(0, 2, 16);
+        graph.add_edge(1, 2, 7);
+        graph.add_edge_directed(2, 3, 8);
+        graph.add_edge_directed(3, 4, 1);
+        graph.add_edge_directed(4, 0, 2);
+        graph.add_edge_directed(1, 4, 30);
+        graph.add_edge_directed(5, 6, 0);
+        graph.add_edge_directed(6, 5, 0);
+
+        let reverse = graph.reversed();
+
+        assert_eq!(reverse.has_edge_directed(3, 2), true);
+        assert_eq!(reverse.has_edge_directed(2, 3), false);
+
+        for source in 0..7 {
+            for target in 0..7 {
+                let expected = graph.shortest_path(source, target);
+                let result = graph.bidirectional_shortest_path_with_reverse(&reverse, source, target);
+
+                assert_eq!(result.cost, expected.cost);
+                assert_eq!(result.path.map(|path| path.cost), expected.path.map(|path| path.cost));
+            }
+        }
+
+        let result = graph.bidirectional_shortest_path(1, 0);
+        assert_eq!(result.cost, Some(6));
+        assert_eq!(graph.restore_path(1, 0, result.parents.unwrap()), std::collections::VecDeque::from([1, 0]));
+
+        let result = graph.bidirectional_shortest_path(2, 1);
+        assert_eq!(result.path.unwrap().nodes, vec![2, 1]);
+        assert_eq!(graph.bidirectional_shortest_path(3, 1).path.unwrap().nodes, vec![3, 4, 0, 1]);
+        assert_eq!(graph.bidirectional_shortest_path(5, 5).path.unwrap().nodes, vec![5]);
+        assert_eq!(graph.bidirectional_shortest_path(5, 6).path.unwrap().nodes, vec![5, 6]);
+        assert_eq!(graph.bidirectional_shortest_path(0, 5), mgraph::DijkstraResult { cost: None, parents: None, path: None });
+    }
+
+    #[test]
+    fn test_graph_contraction_hierarchy() {
+        let cells: Vec<Vec<Option<i32>>> = (0..8).map(|y| (0..8).map(|x| ((x * 3 + y * 5) % 7 != 0).then_some(1 + (x + y) % 3)).collect()).collect();
+        let mut graph: mgraph::Graph = mgraph::Graph::from_grid(&cells, &mgraph::GridOptions::default()).unwrap();
+
+        graph.add_node(100);
+        graph.add_edge_directed(100, 9, 2);
+        graph.add_edge_directed(62, 100, 0);
+
+        let hierarchy = graph.contraction_hierarchy().unwrap();
+
+        assert_eq!(hierarchy.len(), graph.nodes.len());
+
+        for &source in graph.nodes.keys() {
+            for &target in graph.nodes.keys() {
+                let expected = graph.shortest_path(source, target);
+                let result = hierarchy.shortest_path(source, target);
+
+                assert_eq!(result.cost, expected.cost);
+
+                if let Some(path) = result.path {
+                    assert_eq!(graph.path_from_nodes(path.nodes.clone()), Some(path.clone()));
+                    assert_eq!((path.source(), path.target(), path.cost), (source, target, expected.cost.unwrap()));
+                }
+            }
+        }
+
+        assert_eq!(hierarchy.try_shortest_path(1, 7000), Err(mgraph::GraphError::UnknownNode(7000)));
+        assert_eq!(hierarchy.shortest_path(9, 9).path.unwrap().nodes, vec![9]);
+
+        #[cfg(feature = "json")]
+        {
+            let json = serde_json::to_string(&hierarchy).unwrap();
+            let restored: mgraph::ContractionHierarchy = serde_json::from_str(&json).unwrap();
+            assert_eq!(restored.shortest_path(1, 62), hierarchy.shortest_path(1, 62));
+        }
+
+        graph.add_edge_directed(1, 2, -1);
+        assert!(matches!(graph.contraction_hierarchy(), Err(mgraph::GraphError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_graph_spfa() {
+        let mut graph = mgraph::Graph::new();
+
+        for node in 0..6 {
+            graph.add_node(node);
+        }
+
+        graph.add_edge(0, 1, 6);
+        graph.add_edge(0, 2, 16);
+        graph.add_edge(1, 2, 7);
+        graph.add_edge_directed(2, 3, 8);
+        graph.add_edge_directed(3, 1, -2);
+        graph.add_edge_directed(3, 4, -5);
+
+        for source in 0..6 {
+            let expected = graph.bellman_ford(source);
+            let result = graph.spfa(source);
+
+            assert_eq!(result.distances, expected.distances);
+            assert_eq!(result.negative_cycle, None);
+
+            for target in 0..6 {
+                assert_eq!(result.path_to(target).map(|path| path.cost), expected.distance(target));
+            }
+        }
+
+        assert_eq!(graph.spfa(0).path_to(4).unwrap().nodes, vec![0, 1, 2, 3, 4]);
+        assert_eq!(graph.try_spfa(0), graph.try_bellman_ford(0));
+        assert_eq!(graph.try_spfa(7), Err(mgraph::GraphError::UnknownNode(7)));
+
+        graph.add_edge_directed(1, 3, -7);
+
+        let mut cycle = graph.spfa(0).negative_cycle.unwrap();
+        cycle.sort();
+        assert_eq!(cycle, vec![1, 3]);
+        assert!(matches!(graph.try_spfa(2), Err(mgraph::GraphError::NegativeCycle(_))));
+        assert_eq!(graph.spfa(5).negative_cycle, None);
+
+        let empty = mgraph::Graph::new();
+        assert_eq!(empty.bellman_ford(0).distances, HashMap::from([(0, 0)]));
+        assert_eq!(empty.spfa(0).distances, HashMap::from([(0, 0)]));
+        assert_eq!(empty.has_negative_cycle(), false);
+
+        let mut partial = mgraph::Graph::new();
+        partial.add_node(0);
+        partial.add_edge_directed(0, 1, 6);
+        partial.add_edge_directed(0, 2, 3);
+
+        let result = partial.spfa(0);
+        assert_eq!(result.distances, HashMap::from([(0, 0), (1, 6), (2, 3)]));
+        assert_eq!(result.negative_cycle, None);
+        assert_eq!(result.distances, partial.bellman_ford(0).distances);
+
+        let mut overflowing = mgraph::Graph::new();
+        overflowing.add_node(0);
+        overflowing.add_edge_directed(0, 1, i32::MAX);
+        overflowing.add_edge_directed(1, 2, 1);
+        overflowing.add_edge_directed(0, 3, i32::MIN);
+        overflowing.add_edge_directed(3, 4, -1);
+
+        let result = overflowing.spfa(0);
+        assert_eq!(result.distances, HashMap::from([(0, 0), (1, i32::MAX), (3, i32::MIN)]));
+        assert_eq!(result.negative_cycle, None);
+        assert_eq!(result.distances, overflowing.bellman_ford(0).distances);
+    }
+
+    #[test]
+    fn test_graph_k_shortest_paths() {
+        let mut graph = mgraph::Graph::new();
+
+        for node in 0..7 {
+            graph.add_node(node);
+        }
+
+        graph.add_edge_directed(0, 1, 3);
+        graph.add_edge_directed(0, 2, 2);
+        graph.add_edge_directed(1, 3, 4);
+        graph.add_edge_directed(2, 1, 1);
+        graph.add_edge_directed(2, 3, 2);
+        graph.add_edge_directed(2, 4, 3);
+        graph.add_edge_directed(3, 4, 2);
+        graph.add_edge_directed(3, 5, 1);
+        graph.add_edge_directed(4, 5, 2);
+
+        let paths = graph.k_shortest_paths(0, 5, 3);
+
+        assert_eq!(paths.len(), 3);
+        assert_eq!(paths[0], graph.shortest_path(0, 5).path.unwrap());
+        assert_eq!(paths[0].nodes, vec![0, 2, 3, 5]);
+        assert_eq!(paths[1].nodes, vec![0, 2, 4, 5]);
+        assert_eq!(paths[2].cost, 8);
+
+        let paths = graph.k_shortest_paths(0, 5, 10);
+        let costs: Vec<i32> = paths.iter().map(|path| path.cost).collect();
+
+        assert_eq!(costs, vec![5, 7, 8, 8, 8, 11, 11]);
+        assert_eq!(paths.iter().map(|path| path.nodes.clone()).collect::<HashSet<Vec<u32>>>().len(), 7);
+
+        for path in &paths {
+            assert_eq!(path.nodes.iter().collect::<HashSet<&u32>>().len(), path.nodes.len());
+            assert_eq!(graph.path_from_nodes(path.nodes.clone()).unwrap().cost, path.cost);
+        }
+
+        assert_eq!(graph.nodes.values().map(|adjacents| adjacents.len()).sum::<usize>(), 9);
+        assert_eq!(graph.k_shortest_paths(0, 5, 0), Vec::new());
+        assert_eq!(graph.k_shortest_paths(5, 0, 3), Vec::new());
+        assert_eq!(graph.k_shortest_paths(0, 0, 3).len(), 1);
+        assert_eq!(graph.try_k_shortest_paths(5, 0, 3), Err(mgraph::GraphError::NoPath { source: 5, target: 0 }));
+        assert_eq!(graph.try_k_shortest_paths(0, 9, 3), Err(mgraph::GraphError::UnknownNode(9)));
+        assert_eq!(graph.try_k_shortest_paths(6, 5, 0), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn test_graph_view() {
+        let mut graph = mgraph::Graph::new();
+
+        for node in 0..6 {
+            graph.add_node(node);
+        }
+
+        graph.add_edge(0, 1, 6);
+        graph.add_edge(0, 2, 16);
+        graph.add_edge(1, 2, 7);
+        graph.add_edge(2, 3, 8);
+        graph.add_edge(1, 4, 1);
+        graph.add_edge(4, 3, 1);
+
+        assert_eq!(graph.shortest_path(0, 3).path.unwrap().nodes, vec![0, 1, 4, 3]);
+
+        let avoided = HashSet::from([4]);
+        let view = graph.view().with_nodes(|node| !avoided.contains(&node));
+
+        assert_eq!(view.has_node(4), false);
+        assert_eq!(view.shortest_path(0, 3).path.unwrap().nodes, vec![0, 1, 2, 3]);
+        assert_eq!(view.astar(0, 3, |_, _| 0), view.shortest_path(0, 3));
+        assert_eq!(view.shortest_path(0, 4).cost, None);
+        assert_eq!(view.shortest_path(4, 0).cost, None);
+        assert_eq!(view.depth_first_search(0), HashSet::from([0, 1, 2, 3]));
+        assert_eq!(view.depth_first_search(4), HashSet::new());
+        assert_eq!(view.breadth_first_search(0, 3), Some(3));
+
+        let light = graph.view().with_edges(|_, _, weight| weight < 10).with_nodes(|node| node != 1);
+
+        assert_eq!(light.shortest_path(0, 3).cost, None);
+        assert_eq!(light.depth_first_search(2), HashSet::from([2, 3, 4]));
+        assert_eq!(light.depth_first_search(0), HashSet::from([0]));
+
+        let directed = graph.view().with_edges(|source, target, _| source < target);
+        assert_eq!(directed.shortest_path(3, 0).cost, None);
+        assert_eq!(directed.shortest_path(0, 3).cost, Some(21));
+
+        assert_eq!(graph.nodes.len(), 6);
+        assert_eq!(graph.shortest_path(0, 3).cost, Some(8));
+    }
+
+    #[test]
+    fn test_graph_breadth_first_traversal() {
+        let mut graph = mgraph::Graph::new();
+
+        for node in 0..7 {
+            graph.add_node(node);
+        }
+
+        graph.add_edge(0, 1, 100);
+        graph.add_edge(0, 2, 1);
+        graph.add_edge(1, 3, 1);
+        graph.add_edge(2, 4, 1);
+        graph.add_edge(4, 3, 1);
+        graph.add_edge_directed(3, 5, 1);
+
+        let result = graph.breadth_first_traversal(0);
+
+        assert_eq!(result.distances, HashMap::from([(0, 0), (1, 1), (2, 1), (3, 2), (4, 2), (5, 3)]));
+        assert_eq!(result.order.len(), 6);
+        assert_eq!(result.order[0], 0);
+        assert!(result.order.windows(2).all(|pair| result.distances[&pair[0]] <= result.distances[&pair[1]]));
+        assert_eq!(result.path_to(3), Some(vec![0, 1, 3]));
+        assert_eq!(result.path_to(6), None);
+        assert_eq!(result.distance(6), None);
+
+        assert_eq!(graph.unweighted_shortest_path(0, 5), Some(vec![0, 1, 3, 5]));
+        assert_eq!(graph.unweighted_shortest_path(5, 0), None);
+        assert_eq!(graph.unweighted_shortest_path(2, 2), Some(vec![2]));
+        assert_eq!(graph.try_unweighted_shortest_path(5, 0), Err(mgraph::GraphError::NoPath { source: 5, target: 0 }));
+        assert_eq!(graph.try_unweighted_shortest_path(0, 9), Err(mgraph::GraphError::UnknownNode(9)));
+        assert!(graph.try_breadth_first_traversal(9).is_err());
+
+        let limited = graph.breadth_first_traversal_limited(0, 1);
+        assert_eq!(limited.distances.keys().copied().collect::<HashSet<u32>>(), HashSet::from([0, 1, 2]));
+        assert_eq!(graph.breadth_first_traversal_limited(0, 0).order, vec![0]);
+
+        // the search has to terminate on cycles when the target can't be reached
+        assert_eq!(graph.breadth_first_search(0, 6), None);
+        assert_eq!(graph.breadth_first_search(3, 2), Some(2));
+        assert_eq!(graph.view().with_nodes(|node| node != 4).breadth_first_search(2, 3), Some(3));
+    }
+
+    #[test]
+    fn test_graph_traversal_iterators() {
+        let mut graph = mgraph::Graph::new();
+
+        for node in 0..6 {
+            graph.add_node(node);
+        }
+
+        graph.add_edge_directed(0, 1, 1);
+        graph.add_edge_directed(1, 2, 1);
+        graph.add_edge_directed(2, 0, 1);
+        graph.add_edge_directed(0, 3, 1);
+        graph.add_edge_directed(4, 3, 1);
+
+        assert_eq!(graph.dfs(0).next(), Some(0));
+        assert_eq!(graph.dfs(0).collect::<HashSet<u32>>(), HashSet::from([0, 1, 2, 3]));
+
+        let mut dfs = graph.dfs(0);
+        let events: Vec<mgraph::DfsEvent> = dfs.events().collect();
+
+        assert_eq!(events.iter().filter(|event| matches!(event, mgraph::DfsEvent::Discover(_))).count(), 4);
+        assert_eq!(events.iter().filter(|event| matches!(event, mgraph::DfsEvent::TreeEdge(..))).count(), 3);
+        assert!(events.contains(&mgraph::DfsEvent::BackEdge(2, 0, 1)));
+        assert_eq!(events.first(), Some(&mgraph::DfsEvent::Discover(0)));
+        assert_eq!(events.last(), Some(&mgraph::DfsEvent::Finish(0)));
+        assert_eq!(dfs.discovery_time(0), Some(0));
+        assert_eq!(dfs.finish_time(0), Some(7));
+        assert_eq!(dfs.parents().get(&2), Some(&1));
+        assert!(dfs.finish_time(2) < dfs.finish_time(1));
+
+        let mut dfs = graph.dfs(0);
+        assert_eq!(dfs.by_ref().count(), 4);
+
+        dfs.add_root(3);
+        dfs.add_root(4);
+        dfs.add_root(5);
+        assert_eq!(dfs.next_event(), Some(mgraph::DfsEvent::Discover(4)));
+        assert_eq!(dfs.next_event(), Some(mgraph::DfsEvent::CrossEdge(4, 3, 1)));
+        assert_eq!(dfs.collect::<Vec<u32>>(), vec![5]);
+
+        let mut bfs = graph.bfs(4);
+        assert_eq!(bfs.by_ref().collect::<Vec<u32>>(), vec![4, 3]);
+
+        bfs.add_root(0);
+        assert_eq!(bfs.next(), Some(0));
+        assert_eq!(bfs.next(), Some(1));
+        assert_eq!(bfs.distance(2), Some(2));
+        assert_eq!(bfs.parents().get(&2), Some(&1));
+        assert_eq!(bfs.next(), Some(2));
+        assert_eq!(bfs.next(), None);
+    }
+
+    #[test]
+    fn test_graph_connected_components() {
+        let mut graph = mgraph::Graph::new();
+
+        for node in 0..7 {
+            graph.add_node(node);
+        }
+
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 2, 1);
+        graph.add_edge_directed(3, 4, 1);
+        graph.add_edge_directed(5, 4, 1);
+        graph.set_coordinates(6, mgraph::Coordinates::new(1.0, 1.0));
+
+        let components = graph.connected_components();
+
+        assert_eq!(components.len(), 3);
+        assert_eq!(components.components[2], HashSet::from([6]));
+        assert_eq!(components.labels.len(), 7);
+        assert!(components.same_component(3, 5));
+        assert!(!components.same_component(0, 3));
+        assert!(!components.same_component(0, 42));
+        assert_eq!(components.component_of(6), Some(2));
+        assert_eq!(components.component_of(42), None);
+        assert_eq!(components.largest().map(|component| component.len()), Some(3));
+        assert_eq!(graph.component_count(), 3);
+
+        graph.add_edge_directed(2, 3, 1);
+        assert_eq!(graph.component_count(), 2);
+        assert_eq!(graph.largest_component(), HashSet::from([0, 1, 2, 3, 4, 5]));
+
+        graph.retain_largest_component();
+        assert_eq!(graph.nodes.len(), 6);
+        assert_eq!(graph.get_coordinates(6), None);
+        assert_eq!(graph.component_count(), 1);
+
+        assert!(mgraph::Graph::new().connected_components().is_empty());
+        assert!(mgraph::Graph::new().largest_component().is_empty());
+    }
+
+    #[test]
+    fn test_graph_strongly_connected_components() {
+        let mut graph = mgraph::Graph::new();
+
+        for node in 0..7 {
+            graph.add_node(node);
+        }
+
+        graph.add_edge_directed(0, 1, 1);
+        graph.add_edge_directed(1, 2, 1);
+        graph.add_edge_directed(2, 0, 1);
+        graph.add_edge_directed(2, 3, 5);
+        graph.add_edge_directed(3, 4, 1);
+        graph.add_edge_directed(4, 3, 1);
+        graph.add_edge_directed(4, 5, 2);
+        graph.add_edge_directed(1, 5, 3);
+
+        assert!(!graph.is_connected());
+        assert!(!graph.is_weakly_connected());
+        assert!(!graph.is_strongly_connected());
+
+        let components = graph.strongly_connected_components();
+
+        assert_eq!(components.len(), 4);
+        assert!(components.same_component(0, 2));
+        assert!(components.same_component(3, 4));
+        assert!(!components.same_component(2, 3));
+        assert_eq!(components.largest(), Some(&HashSet::from([0, 1, 2])));
+
+        for (node, adjacents) in &graph.nodes {
+            for (adjacent, _) in adjacents {
+                assert!(components.component_of(*node) <= components.component_of(*adjacent));
+            }
+        }
+
+        let (dag, components) = graph.condensation();
+        let label = |node: u32| components.component_of(node).unwrap();
+
+        assert_eq!(dag.nodes.len(), 4);
+        assert_eq!(dag.get_node_adjacents(label(0)).iter().copied().collect::<HashSet<_>>(), HashSet::from([(label(3), 5), (label(5), 3)]));
+        assert_eq!(dag.get_node_adjacents(label(3)).iter().copied().collect::<HashSet<_>>(), HashSet::from([(label(5), 2)]));
+        assert!(dag.get_node_adjacents(label(6)).is_empty());
+        assert!(dag.strongly_connected_components().components.iter().all(|component| component.len() == 1));
+
+        graph.add_edge_directed(5, 6, 1);
+        assert!(graph.is_connected());
+        assert!(!graph.is_strongly_connected());
+
+        graph.add_edge_directed(6, 0, 1);
+        assert!(graph.is_strongly_connected());
+        assert_eq!(graph.condensation().0.nodes.len(), 1);
+    }
+
+    #[test]
+    fn test_graph_topological_sort() {
+        let mut graph = mgraph::Graph::new();
+
+        for node in 0..6 {
+            graph.add_node(node);
+        }
+
+        graph.add_edge_directed(0, 1, 3);
+        graph.add_edge_directed(0, 2, 2);
+        graph.add_edge_directed(1, 3, 4);
+        graph.add_edge_directed(2, 3, 1);
+        graph.add_edge_directed(0, 3, 5);
+        graph.add_edge_directed(3, 4, 2);
+
+        let is_ordered = |order: &Vec<u32>| {
+            let position = |node: u32| order.iter().position(|&other| other == node).unwrap();
+
+            return order.len() == 6 && graph.nodes.iter().all(|(&node, adjacents)| adjacents.iter().all(|&(adjacent, _)| position(node) < position(adjacent)));
+        };
+
+        assert!(graph.topological_sort().is_ok_and(|order| is_ordered(&order)));
+        assert!(graph.topological_sort_dfs().is_ok_and(|order| is_ordered(&order)));
+
+        let critical_path = graph.critical_path().unwrap().unwrap();
+        assert_eq!(critical_path.nodes, vec![0, 1, 3, 4]);
+        assert_eq!(critical_path.cost, 9);
+
+        assert_eq!(graph.longest_path(0, 3).unwrap().map(|path| path.cost), Some(7));
+        assert_eq!(graph.longest_path(2, 4).unwrap().map(|path| path.nodes), Some(vec![2, 3, 4]));
+        assert_eq!(graph.longest_path(4, 0), Ok(None));
+        assert_eq!(graph.longest_path(0, 42), Err(mgraph::GraphError::UnknownNode(42)));
+
+        let reduction = graph.transitive_reduction().unwrap();
+        assert!(!reduction.has_edge_directed(0, 3));
+        assert!(reduction.has_edge_directed(0, 1));
+        assert!(reduction.has_edge_directed(2, 3));
+        assert_eq!(reduction.nodes.values().map(|adjacents| adjacents.len()).sum::<usize>(), 5);
+        assert_eq!(reduction.depth_first_search(0), graph.depth_first_search(0));
+
+        graph.add_edge_directed(4, 1, 1);
+
+        for result in [graph.topological_sort(), graph.topological_sort_dfs()] {
+            let Err(mgraph::GraphError::Cycle(cycle)) = result else {
+                panic!("expected a cycle, got {:?}", result);
+            };
+
+            assert_eq!(cycle.len(), 3);
+            assert!(cycle.iter().all(|node| [1, 3, 4].contains(node)));
+            assert!(graph.has_edge_directed(cycle[2], cycle[0]));
+        }
+
+        assert!(matches!(graph.critical_path(), Err(mgraph::GraphError::Cycle(_))));
+        assert!(matches!(graph.transitive_reduction(), Err(mgraph::GraphError::Cycle(_))));
+        assert_eq!(mgraph::Graph::new().critical_path(), Ok(None));
+    }
+
+    #[test]
+    fn test_graph_cycles() {
+        let mut graph = mgraph::Graph::new();
+
+        for node in 0..5 {
+            graph.add_node(node);
+        }
+
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 2, 1);
+        graph.add_edge(1, 3, 1);
+
+        assert!(graph.has_cycle());
+        assert!(!graph.has_cycle_undirected());
+        assert_eq!(graph.find_cycle().map(|cycle| cycle.len()), Some(2));
+
+        graph.add_edge_directed(3, 0, 1);
+        assert!(graph.has_cycle_undirected());
+
+        let mut graph = mgraph::Graph::new();
+
+        for node in 0..5 {
+            graph.add_node(node);
+        }
+
+        graph.add_edge_directed(0, 1, 1);
+        graph.add_edge_directed(1, 2, 1);
+        graph.add_edge_directed(0, 2, 1);
+        graph.add_edge_directed(2, 3, 1);
+
+        assert!(!graph.has_cycle());
+        assert!(graph.has_cycle_undirected());
+        assert_eq!(graph.find_cycle(), None);
+        assert!(graph.elementary_cycles(10).is_empty());
+
+        graph.add_edge_directed(3, 0, 1);
+        graph.add_edge_directed(3, 0, 2);
+        graph.add_edge_directed(2, 1, 1);
+        graph.nodes.get_mut(&4).unwrap().push((4, 1));
+
+        let cycle = graph.find_cycle().unwrap();
+        assert!(cycle.iter().enumerate().all(|(index, &node)| graph.has_edge_directed(node, cycle[(index + 1) % cycle.len()])));
+
+        let mut cycles: Vec<Vec<u32>> = graph.elementary_cycles(10);
+
+        for cycle in cycles.iter_mut() {
+            let start = cycle.iter().enumerate().min_by_key(|&(_, node)| node).unwrap().0;
+            cycle.rotate_left(start);
+        }
+
+        cycles.sort();
+        assert_eq!(cycles, vec![vec![0, 1, 2, 3], vec![0, 2, 3], vec![1, 2], vec![4]]);
+        assert_eq!(graph.elementary_cycles(2).len(), 2);
+        assert!(graph.elementary_cycles(0).is_empty());
+    }
+
+    #[test]
+    fn float_weights() {
+        let mut graph: mgraph::Graph<u32, f64> = mgraph::Graph::default();
+
+        for node in 0..4 {
+            graph.add_node(node);
+        }
+
+        graph.add_edge(0, 1, 0.5);
+        graph.add_edge(1, 2, 0.25);
+        graph.add_edge(0, 2, 1.0);
+        graph.add_edge(2, 3, 1.5);
+        graph.add_edge(0, 1, 0.5);
+
+        assert_eq!(graph.get_node_adjacents(0), &vec![(1, 0.5), (2, 1.0)]);
+
+        let path = graph.shortest_path(0, 3).path.unwrap();
+        assert_eq!(path.nodes, vec![0, 1, 2, 3]);
+        assert_eq!(path.cost, 2.25);
+
+        assert_eq!(graph.bellman_ford(0).distance(3), Some(2.25));
+        assert_eq!(graph.k_shortest_paths(0, 3, 2).iter().map(|path| path.cost).collect::<Vec<f64>>(), vec![2.25, 2.5]);
+    }
+
+    #[test]
+    fn node_ids() {
+        let mut cities: mgraph::NodeIds<String> = mgraph::NodeIds::new();
+        let mut graph: mgraph::Graph<usize, f32> = mgraph::Graph::default();
+
+        assert!(cities.is_empty());
+
+        let kyiv = cities.insert(String::from("Kyiv"));
+        let lviv = cities.insert(String::from("Lviv"));
+        let odesa = cities.insert(String::from("Odesa"));
+
+        assert_eq!((kyiv, lviv, odesa), (0, 1, 2));
+        assert_eq!(cities.insert(String::from("Lviv")), lviv);
+        assert_eq!(cities.len(), 3);
+        assert_eq!(cities.id(&String::from("Odesa")), Some(odesa));
+        assert_eq!(cities.id(&String::from("Kharkiv")), None);
+        assert_eq!(cities.get(3), None);
+
+        for id in 0..cities.len() {
+            graph.add_node(id);
+        }
+
+        graph.add_edge(kyiv, lviv, 540.5);
+        graph.add_edge(kyiv, odesa, 475.0);
+        graph.add_edge(lviv, odesa, 790.0);
+
+        let path = graph.shortest_path(lviv, odesa).path.unwrap();
+        let names: Vec<&str> = path.nodes.iter().map(|&node| cities.get(node).unwrap().as_str()).collect();
 
-        assert_eq!(result, false);
+        assert_eq!(names, vec!["Lviv", "Odesa"]);
+        assert_eq!(path.cost, 790.0);
     }
 }
@@ -0,0 +1,254 @@
+use crate::edge_list::{index_to_node, invalid_input, node_to_index, parse_token};
+use crate::{EdgeListOptions, Graph, ParseError};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::io;
+use std::io::{BufRead, Write};
+use std::str::FromStr;
+
+impl<N: Copy + Eq + Hash, E: Copy + PartialEq> Graph<N, E> {
+    /// Reads a graph from a Matrix Market coordinate file (`%%MatrixMarket matrix coordinate <field> <symmetry>`).
+    /// Entry `i j value` is an edge from node `i - 1` to node `j - 1`, since Matrix Market indices start at 1.
+    /// `symmetric` matrices always produce edges added with `add_edge()`, `general` ones follow `options.directed`.
+    /// `pattern` matrices have no values, so `options.default_weight` is used as the weight of every edge.
+    pub fn from_matrix_market(matrix_market: &str, options: &EdgeListOptions<E>) -> Result<Self, ParseError>
+    where
+        N: TryFrom<usize>,
+        E: FromStr,
+    {
+        return Self::read_matrix_market(matrix_market.as_bytes(), options);
+    }
+
+    /// Reads a graph from a Matrix Market coordinate file, reading it from `reader`. See `from_matrix_market()`.
+    pub fn read_matrix_market<R: BufRead>(reader: R, options: &EdgeListOptions<E>) -> Result<Self, ParseError>
+    where
+        N: TryFrom<usize>,
+        E: FromStr,
+    {
+        let mut lines = reader.lines().enumerate();
+
+        let header = match lines.next() {
+            Some((_, line)) => line?.to_lowercase(),
+            None => String::new(),
+        };
+        let header: Vec<&str> = header.split_whitespace().collect();
+
+        let (is_pattern, is_symmetric) = match header[..] {
+            ["%%matrixmarket", "matrix", "coordinate", field, symmetry] => {
+                let is_pattern = match field {
+                    "real" | "integer" => false,
+                    "pattern" => true,
+                    _ => return Err(ParseError::Syntax { line: 1, message: format!("unsupported field type: {}", field) }),
+                };
+                let is_symmetric = match symmetry {
+                    "general" => false,
+                    "symmetric" => true,
+                    _ => return Err(ParseError::Syntax { line: 1, message: format!("unsupported symmetry: {}", symmetry) }),
+                };
+
+                (is_pattern, is_symmetric)
+            }
+            _ => return Err(ParseError::Syntax { line: 1, message: String::from("expected %%MatrixMarket matrix coordinate header") }),
+        };
+
+        let directed = options.directed && !is_symmetric;
+        let mut graph = Self::default();
+        let mut size: Option<(usize, usize)> = None;
+        let mut entries = 0;
+
+        for (index, line) in lines {
+            let line = line?;
+            let line_number = index + 1;
+            let columns: Vec<&str> = line.split_whitespace().collect();
+
+            if columns.is_empty() || line.starts_with('%') {
+                continue;
+            }
+
+            let Some((node_count, expected)) = size else {
+                let [rows, columns_count, entry_count] = columns[..] else {
+                    return Err(ParseError::Syntax { line: line_number, message: String::from("expected rows, columns and entries") });
+                };
+                let node_count = parse_token::<usize>(rows, line_number, "row count")?.max(parse_token(columns_count, line_number, "column count")?);
+
+                for index in 0..node_count {
+                    graph.add_node(index_to_node(index, line_number)?);
+                }
+
+                size = Some((node_count, parse_token(entry_count, line_number, "entry count")?));
+                continue;
+            };
+
+            let (row, column, weight) = match (&columns[..], is_pattern) {
+                ([row, column], true) => {
+                    let weight = options.default_weight.ok_or_else(|| ParseError::Syntax {
+                        line: line_number,
+                        message: String::from("pattern matrix requires a default weight"),
+                    })?;
+
+                    (row, column, weight)
+                }
+                ([row, column, weight], false) => (row, column, parse_token(weight, line_number, "edge weight")?),
+                _ => return Err(ParseError::Syntax { line: line_number, message: format!("unexpected entry: {}", line) }),
+            };
+
+            let row: usize = parse_token(row, line_number, "row")?;
+            let column: usize = parse_token(column, line_number, "column")?;
+
+            if !(1..=node_count).contains(&row) || !(1..=node_count).contains(&column) {
+                return Err(ParseError::Syntax { line: line_number, message: format!("entry {} {} is out of matrix bounds", row, column) });
+            }
+
+            graph.insert_edge(index_to_node(row - 1, line_number)?, index_to_node(column - 1, line_number)?, weight, directed);
+
+            entries += 1;
+            if entries > expected {
+                return Err(ParseError::Syntax { line: line_number, message: format!("more than {} entries", expected) });
+            }
+        }
+
+        match size {
+            Some((_, expected)) if entries == expected => return Ok(graph),
+            Some((_, expected)) => return Err(ParseError::Invalid(format!("expected {} entries, found {}", expected, entries))),
+            None => return Err(ParseError::Invalid(String::from("missing size line"))),
+        }
+    }
+
+    /// Writes a graph as a `general` Matrix Market coordinate file. Node `i` is written as index `i + 1`, so nodes have to be numbered from 0.
+    pub fn to_matrix_market(&self) -> io::Result<String>
+    where
+        usize: TryFrom<N>,
+        E: Display,
+    {
+        let mut buffer: Vec<u8> = Vec::new();
+        self.write_matrix_market(&mut buffer)?;
+
+        return Ok(String::from_utf8(buffer).unwrap());
+    }
+
+    /// Writes a graph as a `general` Matrix Market coordinate file to `writer`. See `to_matrix_market()`.
+    pub fn write_matrix_market<W: Write>(&self, mut writer: W) -> io::Result<()>
+    where
+        usize: TryFrom<N>,
+        E: Display,
+    {
+        let node_count = self.node_index_bound()?;
+        let entry_count: usize = self.nodes.values().map(|adjacents| adjacents.len()).sum();
+
+        writeln!(writer, "%%MatrixMarket matrix coordinate real general")?;
+        writeln!(writer, "{} {} {}", node_count, node_count, entry_count)?;
+
+        for (&source, adjacents) in &self.nodes {
+            for &(target, weight) in adjacents {
+                writeln!(writer, "{} {} {}", node_to_index(source)? + 1, node_to_index(target)? + 1, weight)?;
+            }
+        }
+
+        return Ok(());
+    }
+
+    /// Reads a graph from a dense adjacency matrix: one whitespace-separated row of weights per line, where a weight equal to `E::default()`
+    /// (`0` for numbers, also written as e.g. `00` or `-0`) means no edge.
+    /// Row `i` describes edges going out of node `i`, nodes are numbered from 0. Comment lines are skipped according to `options`.
+    pub fn from_adjacency_matrix(matrix: &str, options: &EdgeListOptions<E>) -> Result<Self, ParseError>
+    where
+        N: TryFrom<usize>,
+        E: FromStr + Default,
+    {
+        return Self::read_adjacency_matrix(matrix.as_bytes(), options);
+    }
+
+    /// Reads a graph from a dense adjacency matrix, reading it from `reader`. See `from_adjacency_matrix()`.
+    pub fn read_adjacency_matrix<R: BufRead>(reader: R, options: &EdgeListOptions<E>) -> Result<Self, ParseError>
+    where
+        N: TryFrom<usize>,
+        E: FromStr + Default,
+    {
+        let mut rows: Vec<(usize, Vec<String>)> = Vec::new();
+
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+
+            if !line.is_empty() && !options.is_comment(line) {
+                rows.push((index + 1, line.split_whitespace().map(String::from).collect()));
+            }
+        }
+
+        let mut graph = Self::default();
+
+        for (index, (line_number, _)) in rows.iter().enumerate() {
+            graph.add_node(index_to_node(index, *line_number)?);
+        }
+
+        for (source, (line_number, row)) in rows.iter().enumerate() {
+            if row.len() != rows.len() {
+                return Err(ParseError::Syntax { line: *line_number, message: format!("expected {} columns, found {}", rows.len(), row.len()) });
+            }
+
+            for (target, entry) in row.iter().enumerate() {
+                let weight: E = parse_token(entry, *line_number, "edge weight")?;
+
+                if weight != E::default() {
+                    graph.insert_edge(index_to_node(source, *line_number)?, index_to_node(target, *line_number)?, weight, options.directed);
+                }
+            }
+        }
+
+        return Ok(graph);
+    }
+
+    /// Writes a graph as a dense adjacency matrix, where `E::default()` (`0` for numbers) means no edge. Nodes have to be numbered from 0 without gaps.
+    /// Returns an error if there are several edges with different weights between the same pair of nodes, or an edge of weight `E::default()`,
+    /// which would be read back as no edge.
+    pub fn to_adjacency_matrix(&self) -> io::Result<String>
+    where
+        usize: TryFrom<N>,
+        E: Display + Default,
+    {
+        let mut buffer: Vec<u8> = Vec::new();
+        self.write_adjacency_matrix(&mut buffer)?;
+
+        return Ok(String::from_utf8(buffer).unwrap());
+    }
+
+    /// Writes a graph as a dense adjacency matrix to `writer`. See `to_adjacency_matrix()`.
+    pub fn write_adjacency_matrix<W: Write>(&self, mut writer: W) -> io::Result<()>
+    where
+        usize: TryFrom<N>,
+        E: Display + Default,
+    {
+        let node_count = self.node_index_bound()?;
+
+        if node_count != self.nodes.len() {
+            return Err(invalid_input(String::from("nodes have to be numbered from 0 without gaps")));
+        }
+
+        let mut matrix: HashMap<(usize, usize), E> = HashMap::new();
+
+        for (&source, adjacents) in &self.nodes {
+            for &(target, weight) in adjacents {
+                let cell = (node_to_index(source)?, node_to_index(target)?);
+
+                if weight == E::default() {
+                    return Err(invalid_input(format!("edge from {} to {} has weight {}, which means no edge", cell.0, cell.1, weight)));
+                }
+
+                if matrix.insert(cell, weight).is_some() {
+                    return Err(invalid_input(format!("several edges from {} to {}", cell.0, cell.1)));
+                }
+            }
+        }
+
+        for row in 0..node_count {
+            let entries: Vec<String> = (0..node_count)
+                .map(|column| matrix.get(&(row, column)).copied().unwrap_or_default().to_string())
+                .collect();
+
+            writeln!(writer, "{}", entries.join(" "))?;
+        }
+
+        return Ok(());
+    }
+}
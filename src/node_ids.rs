@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Assigns `usize` ids to node payloads which can't be nodes themselves, e.g. `String` names or structs with owned data.
+/// Ids are given out in insertion order starting at 0, so they can be used as nodes of a `Graph<usize, E>`.
+#[derive(Debug, Clone)]
+pub struct NodeIds<T> {
+    payloads: Vec<T>,
+    ids: HashMap<T, usize>,
+}
+
+impl<T: Clone + Eq + Hash> NodeIds<T> {
+    /// Creates an empty map of ids.
+    pub fn new() -> Self {
+        return NodeIds { payloads: Vec::new(), ids: HashMap::new() };
+    }
+
+    /// Returns the id of `payload`, assigning the next free id if it wasn't inserted before.
+    pub fn insert(&mut self, payload: T) -> usize {
+        if let Some(&id) = self.ids.get(&payload) {
+            return id;
+        }
+
+        let id = self.payloads.len();
+
+        self.ids.insert(payload.clone(), id);
+        self.payloads.push(payload);
+
+        return id;
+    }
+
+    /// Returns the id of `payload`, or `None` if it wasn't inserted.
+    pub fn id(&self, payload: &T) -> Option<usize> {
+        return self.ids.get(payload).copied();
+    }
+
+    /// Returns the payload with id `id`, or `None` if there is no such id.
+    pub fn get(&self, id: usize) -> Option<&T> {
+        return self.payloads.get(id);
+    }
+
+    /// Returns the number of ids given out.
+    pub fn len(&self) -> usize {
+        return self.payloads.len();
+    }
+
+    /// Checks if no ids were given out.
+    pub fn is_empty(&self) -> bool {
+        return self.payloads.is_empty();
+    }
+}
+
+impl<T: Clone + Eq + Hash> Default for NodeIds<T> {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
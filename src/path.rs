@@ -0,0 +1,112 @@
+use crate::{compare_weights, Graph, Weight};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A path through a graph: the nodes it visits in order, the weights of the edges between them and the total cost.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Path<N = u32, E = i32> {
+    /// Nodes of the path, from source to target. A path always contains at least one node.
+    pub nodes: Vec<N>,
+    /// Weights of the edges of the path, `weights[i]` is the weight of the edge from `nodes[i]` to `nodes[i + 1]`.
+    pub weights: Vec<E>,
+    /// Sum of all weights of the path.
+    pub cost: E,
+}
+
+impl<N: Copy, E: Copy> Path<N, E> {
+    /// Returns the first node of the path.
+    pub fn source(&self) -> N {
+        return self.nodes[0];
+    }
+
+    /// Returns the last node of the path.
+    pub fn target(&self) -> N {
+        return self.nodes[self.nodes.len() - 1];
+    }
+
+    /// Iterates over edges of the path as `(source, target, weight)`.
+    pub fn edges(&self) -> impl Iterator<Item = (N, N, E)> + '_ {
+        return self.nodes.windows(2).zip(&self.weights).map(|(pair, &weight)| (pair[0], pair[1], weight));
+    }
+}
+
+/// Shortest paths from one source to every node reachable from it, returned by `Graph::shortest_path_tree()`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(
+    serialize = "N: serde::Serialize, E: serde::Serialize",
+    deserialize = "N: serde::Deserialize<'de> + Eq + Hash, E: serde::Deserialize<'de>"
+)))]
+pub struct ShortestPathTree<N = u32, E = i32> {
+    /// Node the paths start from.
+    pub source: N,
+    /// Map of every reachable node and the cost of the shortest path to it.
+    pub distances: HashMap<N, E>,
+    /// Map of every reachable node except `source` and its predecessor on the shortest path, together with the weight of the edge between them.
+    pub parents: HashMap<N, (N, E)>,
+}
+
+impl<N: Eq + Hash, E: PartialEq> PartialEq for ShortestPathTree<N, E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source && self.distances == other.distances && self.parents == other.parents
+    }
+}
+
+impl<N: Copy + Eq + Hash, E: Weight> ShortestPathTree<N, E> {
+    /// Returns the cost of the shortest path to `target`, or `None` if it can't be reached.
+    pub fn distance(&self, target: N) -> Option<E> {
+        return self.distances.get(&target).copied();
+    }
+
+    /// Returns the shortest path from `source` to `target`, or `None` if it can't be reached.
+    pub fn path_to(&self, target: N) -> Option<Path<N, E>> {
+        return path_from_parents(self.source, target, &self.parents);
+    }
+}
+
+/// Restores the path from `source` to `target` from a map of a node and its predecessor together with the weight of the edge between them.
+pub(crate) fn path_from_parents<N: Copy + Eq + Hash, E: Weight>(source: N, target: N, parents: &HashMap<N, (N, E)>) -> Option<Path<N, E>> {
+    let mut nodes: Vec<N> = vec![target];
+    let mut weights: Vec<E> = Vec::new();
+    let mut current_node = target;
+
+    while current_node != source {
+        if nodes.len() > parents.len() {
+            return None;
+        }
+
+        let &(parent, weight) = parents.get(&current_node)?;
+        nodes.push(parent);
+        weights.push(weight);
+        current_node = parent;
+    }
+
+    nodes.reverse();
+    weights.reverse();
+    let cost = weights.iter().fold(E::zero(), |cost, &weight| cost + weight);
+
+    return Some(Path { nodes, weights, cost });
+}
+
+impl<N: Copy + Eq + Hash, E: Weight> Graph<N, E> {
+    /// Builds a `Path` visiting `nodes` in order, using the lightest edge between every pair of consecutive nodes.
+    /// Returns `None` if `nodes` is empty or two consecutive nodes are not connected.
+    pub fn path_from_nodes(&self, nodes: Vec<N>) -> Option<Path<N, E>> {
+        if nodes.is_empty() {
+            return None;
+        }
+
+        let mut weights: Vec<E> = Vec::with_capacity(nodes.len() - 1);
+        let mut cost = E::zero();
+
+        for pair in nodes.windows(2) {
+            let weight = self.adjacents(pair[0]).filter(|&&(target, _)| target == pair[1]).map(|&(_, weight)| weight).min_by(compare_weights)?;
+
+            weights.push(weight);
+            cost = cost + weight;
+        }
+
+        return Some(Path { nodes, weights, cost });
+    }
+}
@@ -0,0 +1,67 @@
+use crate::{Coordinates, Graph, ParseError};
+use serde::de::Error;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// Layout of a serialized graph, `coordinates` may be left out.
+#[derive(Deserialize)]
+#[serde(bound(deserialize = "N: Deserialize<'de> + Eq + Hash, E: Deserialize<'de>"))]
+pub(crate) struct SerializedGraph<N, E> {
+    pub(crate) nodes: HashMap<N, Vec<(N, E)>>,
+    #[serde(default)]
+    pub(crate) coordinates: HashMap<N, Coordinates>,
+}
+
+/// Graphs are serialized as a struct with the adjacency map in `nodes` and node coordinates in `coordinates`,
+/// the same layout `Graph::serialize()` produces. `coordinates` is left out if no node has coordinates.
+impl<N: Serialize, E: Serialize> Serialize for Graph<N, E> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut graph = serializer.serialize_struct("Graph", if self.coordinates.is_empty() { 1 } else { 2 })?;
+
+        graph.serialize_field("nodes", &self.nodes)?;
+
+        if self.coordinates.is_empty() {
+            graph.skip_field("coordinates")?;
+        } else {
+            graph.serialize_field("coordinates", &self.coordinates)?;
+        }
+
+        return graph.end();
+    }
+}
+
+/// Deserialization fails if an edge or coordinates point to a node which is not present in the graph.
+impl<'de, N, E> Deserialize<'de> for Graph<N, E>
+where
+    N: Deserialize<'de> + Copy + Eq + Hash + Debug,
+    E: Deserialize<'de> + Copy + PartialEq,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        return Graph::from_serialized(SerializedGraph::deserialize(deserializer)?).map_err(D::Error::custom);
+    }
+}
+
+impl<N: Copy + Eq + Hash + Debug, E> Graph<N, E> {
+    /// Builds a graph from its serialized layout, checking that every edge and coordinates point to a node of the graph.
+    pub(crate) fn from_serialized(graph: SerializedGraph<N, E>) -> Result<Self, ParseError> {
+        let SerializedGraph { nodes, coordinates } = graph;
+
+        for (source, adjacents) in &nodes {
+            if let Some((target, _)) = adjacents.iter().find(|(target, _)| !nodes.contains_key(target)) {
+                return Err(ParseError::DanglingEdge {
+                    source: format!("{:?}", source),
+                    target: format!("{:?}", target),
+                });
+            }
+        }
+
+        if let Some(node) = coordinates.keys().find(|node| !nodes.contains_key(node)) {
+            return Err(ParseError::Invalid(format!("coordinates of unknown node {:?}", node)));
+        }
+
+        return Ok(Self { nodes, coordinates });
+    }
+}
@@ -0,0 +1,138 @@
+use crate::{Graph, GraphError, Weight};
+use std::hash::Hash;
+
+/// Mean radius of the Earth in meters, used by `Heuristic::Haversine`.
+const EARTH_RADIUS: f64 = 6_371_000.0;
+
+/// Position of a node in space. 2D coordinates have `z` equal to 0.
+/// Geographic coordinates store longitude in `x` and latitude in `y`, both in degrees.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Coordinates {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Coordinates {
+    /// Creates 2D coordinates.
+    pub fn new(x: f64, y: f64) -> Self {
+        return Self { x, y, z: 0.0 };
+    }
+
+    /// Creates 3D coordinates.
+    pub fn new_3d(x: f64, y: f64, z: f64) -> Self {
+        return Self { x, y, z };
+    }
+
+    /// Creates geographic coordinates from latitude and longitude in degrees.
+    pub fn from_lat_lon(latitude: f64, longitude: f64) -> Self {
+        return Self { x: longitude, y: latitude, z: 0.0 };
+    }
+
+    /// Returns the latitude of geographic coordinates in degrees.
+    pub fn latitude(&self) -> f64 {
+        return self.y;
+    }
+
+    /// Returns the longitude of geographic coordinates in degrees.
+    pub fn longitude(&self) -> f64 {
+        return self.x;
+    }
+}
+
+/// Distance between node coordinates used as an A* heuristic, see `Graph::heuristic()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Heuristic {
+    /// Sum of the differences along every axis. Suits grids where moves go along the axes only.
+    Manhattan,
+    /// Straight-line distance. Suits graphs where edges can go in any direction.
+    Euclidean,
+    /// Largest difference along any axis. Suits grids where diagonal moves cost the same as straight ones.
+    Chebyshev,
+    /// Distance on a 2D grid where diagonal moves cost √2 times a straight move.
+    Octile,
+    /// Great-circle distance in meters between geographic coordinates, see `Coordinates::from_lat_lon()`.
+    Haversine,
+}
+
+impl Heuristic {
+    /// Returns the distance between two points.
+    pub fn distance(&self, source: Coordinates, target: Coordinates) -> f64 {
+        let dx = (source.x - target.x).abs();
+        let dy = (source.y - target.y).abs();
+        let dz = (source.z - target.z).abs();
+
+        match self {
+            Heuristic::Manhattan => return dx + dy + dz,
+            Heuristic::Euclidean => return (dx * dx + dy * dy + dz * dz).sqrt(),
+            Heuristic::Chebyshev => return dx.max(dy).max(dz),
+            Heuristic::Octile => return dx.max(dy) + (std::f64::consts::SQRT_2 - 1.0) * dx.min(dy),
+            Heuristic::Haversine => {
+                let latitude_delta = (target.latitude() - source.latitude()).to_radians();
+                let longitude_delta = (target.longitude() - source.longitude()).to_radians();
+
+                let a = (latitude_delta / 2.0).sin().powi(2)
+                    + source.latitude().to_radians().cos() * target.latitude().to_radians().cos() * (longitude_delta / 2.0).sin().powi(2);
+
+                return 2.0 * EARTH_RADIUS * a.sqrt().min(1.0).asin();
+            }
+        }
+    }
+}
+
+impl<N: Copy + Eq + Hash, E: Copy + PartialEq> Graph<N, E> {
+    /// Sets coordinates of a node. Does nothing if the node is not present in the graph.
+    pub fn set_coordinates(&mut self, node: N, coordinates: Coordinates) {
+        if self.has_node(node) {
+            self.coordinates.insert(node, coordinates);
+        }
+    }
+
+    /// Sets coordinates of a node like `set_coordinates()`.
+    /// Returns `GraphError::UnknownNode` if the node is not present in the graph.
+    pub fn try_set_coordinates(&mut self, node: N, coordinates: Coordinates) -> Result<(), GraphError<N>> {
+        self.check_node(node)?;
+        self.coordinates.insert(node, coordinates);
+
+        return Ok(());
+    }
+
+    /// Returns coordinates of a node, or `None` if they are not set.
+    pub fn get_coordinates(&self, node: N) -> Option<Coordinates> {
+        return self.coordinates.get(&node).copied();
+    }
+
+    /// Removes coordinates of a node and returns them, or `None` if they were not set.
+    pub fn remove_coordinates(&mut self, node: N) -> Option<Coordinates> {
+        return self.coordinates.remove(&node);
+    }
+}
+
+impl<N: Copy + Eq + Hash, E: Weight> Graph<N, E> {
+    /// Builds an A* heuristic which returns the `heuristic` distance between coordinates of the nodes, rounded down.
+    /// Returns 0 for nodes without coordinates, so the heuristic stays admissible as long as every edge costs at least the distance between its nodes.
+    pub fn heuristic(&self, heuristic: Heuristic) -> impl Fn(N, N) -> E + '_ {
+        return self.scaled_heuristic(heuristic, 1.0);
+    }
+
+    /// Builds an A* heuristic like `heuristic()`, multiplying distances by `scale`.
+    /// Use it when edge weights are in other units than coordinates, e.g. a scale of 10 for grids where a straight move costs 10.
+    pub fn scaled_heuristic(&self, heuristic: Heuristic, scale: f64) -> impl Fn(N, N) -> E + '_ {
+        return scaled_coordinate_heuristic(heuristic, scale, |node| self.get_coordinates(node));
+    }
+
+    /// Builds an A* heuristic like `heuristic()`, taking coordinates from `coordinates(node)` instead of the graph, e.g. to compute them from node ids.
+    /// Returns 0 for nodes for which `coordinates` returns `None`.
+    pub fn coordinate_heuristic<'a>(&self, heuristic: Heuristic, coordinates: impl Fn(N) -> Option<Coordinates> + 'a) -> impl Fn(N, N) -> E + 'a {
+        return scaled_coordinate_heuristic(heuristic, 1.0, coordinates);
+    }
+}
+
+/// Builds an A* heuristic which returns the `heuristic` distance between `coordinates` of the nodes multiplied by `scale`, or 0 if either has none.
+fn scaled_coordinate_heuristic<'a, N, E: Weight>(heuristic: Heuristic, scale: f64, coordinates: impl Fn(N) -> Option<Coordinates> + 'a) -> impl Fn(N, N) -> E + 'a {
+    return move |node, target| match (coordinates(node), coordinates(target)) {
+        (Some(node), Some(target)) => E::from_distance(heuristic.distance(node, target) * scale),
+        _ => E::zero(),
+    };
+}
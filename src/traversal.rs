@@ -0,0 +1,315 @@
+use crate::{Graph, GraphError};
+use std::collections::hash_map::Entry;
+use std::slice;
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+type Adjacents<'a, N, E> = Option<slice::Iter<'a, (N, E)>>;
+
+/// Event of a depth-first traversal, returned by `Dfs::next_event()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DfsEvent<N = u32, E = i32> {
+    /// Node is visited for the first time (pre-order).
+    Discover(N),
+    /// Edge `(source, target, weight)` leads to a node which is not visited yet and becomes part of the DFS tree.
+    TreeEdge(N, N, E),
+    /// Edge leads to a node whose visit is in progress, so it closes a cycle.
+    /// In graphs built with `add_edge()`, the edge going back to the parent of a node is also reported as a back edge.
+    BackEdge(N, N, E),
+    /// Edge leads to a node which is already finished (a forward or a cross edge).
+    CrossEdge(N, N, E),
+    /// All edges of the node are explored (post-order).
+    Finish(N),
+}
+
+/// Lazy depth-first traversal of a graph, created by `Graph::dfs()`. Iterating yields nodes in pre-order.
+/// Use `next_event()` for edge classification and post-order, and `add_root()` to continue from other nodes, e.g. to traverse every node of the graph.
+pub struct Dfs<'a, N = u32, E = i32> {
+    graph: &'a Graph<N, E>,
+    roots: VecDeque<N>,
+    stack: Vec<(N, Adjacents<'a, N, E>)>,
+    pending: Option<DfsEvent<N, E>>,
+    time: usize,
+    discovery_times: HashMap<N, usize>,
+    finish_times: HashMap<N, usize>,
+    parents: HashMap<N, N>,
+}
+
+impl<'a, N: Copy + Eq + Hash, E: Copy + PartialEq> Dfs<'a, N, E> {
+    /// Adds a node to continue the traversal from once everything reachable from the previous roots is visited.
+    /// Nodes visited by the time the root is reached are skipped.
+    pub fn add_root(&mut self, root: N) {
+        self.roots.push_back(root);
+    }
+
+    /// Returns the next event of the traversal, or `None` when every node reachable from the roots is finished.
+    pub fn next_event(&mut self) -> Option<DfsEvent<N, E>> {
+        if let Some(event) = self.pending.take() {
+            return Some(event);
+        }
+
+        if let Some((node, adjacents)) = self.stack.last_mut() {
+            let node = *node;
+
+            let Some(&(adjacent, weight)) = adjacents.as_mut().and_then(|adjacents| adjacents.next()) else {
+                self.stack.pop();
+
+                let time = self.tick();
+                self.finish_times.insert(node, time);
+
+                return Some(DfsEvent::Finish(node));
+            };
+
+            if !self.discovery_times.contains_key(&adjacent) {
+                self.parents.insert(adjacent, node);
+                self.pending = Some(self.discover(adjacent));
+
+                return Some(DfsEvent::TreeEdge(node, adjacent, weight));
+            } else if !self.finish_times.contains_key(&adjacent) {
+                return Some(DfsEvent::BackEdge(node, adjacent, weight));
+            } else {
+                return Some(DfsEvent::CrossEdge(node, adjacent, weight));
+            }
+        }
+
+        while let Some(root) = self.roots.pop_front() {
+            if !self.discovery_times.contains_key(&root) {
+                return Some(self.discover(root));
+            }
+        }
+
+        return None;
+    }
+
+    /// Returns an iterator over the remaining events of the traversal.
+    pub fn events(&mut self) -> impl Iterator<Item = DfsEvent<N, E>> + use<'_, 'a, N, E> {
+        return std::iter::from_fn(move || self.next_event());
+    }
+
+    /// Returns the time `node` was discovered at, or `None` if it's not discovered yet. Discovery and finish share one clock starting at 0.
+    pub fn discovery_time(&self, node: N) -> Option<usize> {
+        return self.discovery_times.get(&node).copied();
+    }
+
+    /// Returns the time `node` was finished at, or `None` if it's not finished yet.
+    pub fn finish_time(&self, node: N) -> Option<usize> {
+        return self.finish_times.get(&node).copied();
+    }
+
+    /// Returns the map of every discovered node except roots and the node it was discovered from, which forms the DFS tree.
+    pub fn parents(&self) -> &HashMap<N, N> {
+        return &self.parents;
+    }
+
+    fn discover(&mut self, node: N) -> DfsEvent<N, E> {
+        let time = self.tick();
+
+        self.discovery_times.insert(node, time);
+        self.stack.push((node, self.graph.nodes.get(&node).map(|adjacents| adjacents.iter())));
+
+        return DfsEvent::Discover(node);
+    }
+
+    fn tick(&mut self) -> usize {
+        self.time += 1;
+
+        return self.time - 1;
+    }
+}
+
+impl<N: Copy + Eq + Hash, E: Copy + PartialEq> Iterator for Dfs<'_, N, E> {
+    type Item = N;
+
+    fn next(&mut self) -> Option<N> {
+        while let Some(event) = self.next_event() {
+            if let DfsEvent::Discover(node) = event {
+                return Some(node);
+            }
+        }
+
+        return None;
+    }
+}
+
+/// Lazy breadth-first traversal of a graph, created by `Graph::bfs()`. Iterating yields nodes in the order of visiting.
+/// Use `add_root()` to continue from other nodes, e.g. to traverse every node of the graph.
+pub struct Bfs<'a, N = u32, E = i32> {
+    graph: &'a Graph<N, E>,
+    roots: VecDeque<N>,
+    queue: VecDeque<N>,
+    distances: HashMap<N, usize>,
+    parents: HashMap<N, N>,
+}
+
+impl<N: Copy + Eq + Hash, E: Copy + PartialEq> Bfs<'_, N, E> {
+    /// Adds a node to continue the traversal from once everything reachable from the previous roots is visited.
+    /// Nodes visited by the time the root is reached are skipped.
+    pub fn add_root(&mut self, root: N) {
+        self.roots.push_back(root);
+    }
+
+    /// Returns the number of edges from the root of its tree to `node`, or `None` if it's not reached yet.
+    pub fn distance(&self, node: N) -> Option<usize> {
+        return self.distances.get(&node).copied();
+    }
+
+    /// Returns the map of every reached node except roots and the node it was reached from, which forms the BFS tree.
+    pub fn parents(&self) -> &HashMap<N, N> {
+        return &self.parents;
+    }
+}
+
+impl<N: Copy + Eq + Hash, E: Copy + PartialEq> Iterator for Bfs<'_, N, E> {
+    type Item = N;
+
+    fn next(&mut self) -> Option<N> {
+        if self.queue.is_empty() {
+            let root = std::iter::from_fn(|| self.roots.pop_front()).find(|root| !self.distances.contains_key(root))?;
+
+            self.distances.insert(root, 0);
+            self.queue.push_back(root);
+        }
+
+        let node = self.queue.pop_front()?;
+        let distance = self.distances[&node];
+
+        for &(adjacent, _) in self.graph.adjacents(node) {
+            if let Entry::Vacant(entry) = self.distances.entry(adjacent) {
+                entry.insert(distance + 1);
+                self.parents.insert(adjacent, node);
+                self.queue.push_back(adjacent);
+            }
+        }
+
+        return Some(node);
+    }
+}
+
+/// Result of a breadth-first traversal, returned by `Graph::breadth_first_traversal()`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(
+    serialize = "N: serde::Serialize",
+    deserialize = "N: serde::Deserialize<'de> + Eq + Hash"
+)))]
+pub struct BfsResult<N = u32> {
+    /// Node the traversal starts from.
+    pub source: N,
+    /// Map of every visited node and the number of edges on the shortest path to it.
+    pub distances: HashMap<N, usize>,
+    /// Map of every visited node except `source` and the node it was first reached from.
+    pub parents: HashMap<N, N>,
+    /// Visited nodes in the order of visiting, starting with `source`.
+    pub order: Vec<N>,
+}
+
+impl<N: Copy + Eq + Hash> BfsResult<N> {
+    /// Returns the number of edges on the shortest path to `target`, or `None` if it was not visited.
+    pub fn distance(&self, target: N) -> Option<usize> {
+        return self.distances.get(&target).copied();
+    }
+
+    /// Returns nodes of the path with the fewest edges from `source` to `target`, or `None` if it was not visited.
+    pub fn path_to(&self, target: N) -> Option<Vec<N>> {
+        let mut path: Vec<N> = Vec::with_capacity(self.distance(target)? + 1);
+        let mut current_node = target;
+
+        path.push(current_node);
+
+        while current_node != self.source {
+            current_node = self.parents[&current_node];
+            path.push(current_node);
+        }
+
+        path.reverse();
+
+        return Some(path);
+    }
+}
+
+impl<N: Copy + Eq + Hash, E: Copy + PartialEq> Graph<N, E> {
+    /// Starts a lazy depth-first traversal from `root`. See `Dfs`.
+    pub fn dfs(&self, root: N) -> Dfs<'_, N, E> {
+        return Dfs {
+            graph: self,
+            roots: VecDeque::from([root]),
+            stack: Vec::new(),
+            pending: None,
+            time: 0,
+            discovery_times: HashMap::new(),
+            finish_times: HashMap::new(),
+            parents: HashMap::new(),
+        };
+    }
+
+    /// Starts a lazy breadth-first traversal from `root`. See `Bfs`.
+    pub fn bfs(&self, root: N) -> Bfs<'_, N, E> {
+        return Bfs { graph: self, roots: VecDeque::from([root]), queue: VecDeque::new(), distances: HashMap::new(), parents: HashMap::new() };
+    }
+
+    /// Visits every node reachable from `source` in breadth-first order, finding the fewest edges needed to reach each of them.
+    /// Edge weights are ignored.
+    pub fn breadth_first_traversal(&self, source: N) -> BfsResult<N> {
+        return self.breadth_first_filtered(source, None, None, |_, _, _| true);
+    }
+
+    /// Visits nodes reachable from `source` like `breadth_first_traversal()`, but only those at most `max_hops` edges away.
+    pub fn breadth_first_traversal_limited(&self, source: N, max_hops: usize) -> BfsResult<N> {
+        return self.breadth_first_filtered(source, Some(max_hops), None, |_, _, _| true);
+    }
+
+    /// Runs `breadth_first_traversal()`, returning `GraphError::UnknownNode` if `source` is not present in the graph.
+    pub fn try_breadth_first_traversal(&self, source: N) -> Result<BfsResult<N>, GraphError<N>> {
+        self.check_node(source)?;
+
+        return Ok(self.breadth_first_traversal(source));
+    }
+
+    /// Finds the path with the fewest edges from `source` to `target`, ignoring edge weights. Returns its nodes, or `None` if there is no path.
+    pub fn unweighted_shortest_path(&self, source: N, target: N) -> Option<Vec<N>> {
+        return self.breadth_first_filtered(source, None, Some(target), |_, _, _| true).path_to(target);
+    }
+
+    /// Finds the path with the fewest edges like `unweighted_shortest_path()`.
+    /// Returns `GraphError::UnknownNode` if `source` or `target` is not present in the graph and `GraphError::NoPath` if `target` can't be reached.
+    pub fn try_unweighted_shortest_path(&self, source: N, target: N) -> Result<Vec<N>, GraphError<N>> {
+        self.check_node(source)?;
+        self.check_node(target)?;
+
+        return self.unweighted_shortest_path(source, target).ok_or(GraphError::NoPath { source, target });
+    }
+
+    /// Runs breadth-first search from `source` using only edges `(source, target, weight)` for which `is_allowed` returns `true`.
+    /// Doesn't go further than `max_hops` edges from `source` and stops as soon as `target` is visited.
+    pub(crate) fn breadth_first_filtered(&self, source: N, max_hops: Option<usize>, target: Option<N>, is_allowed: impl Fn(N, N, E) -> bool) -> BfsResult<N> {
+        let mut result = BfsResult { source, distances: HashMap::from([(source, 0)]), parents: HashMap::new(), order: vec![source] };
+        let mut queue: VecDeque<N> = VecDeque::from([source]);
+
+        while let Some(node) = queue.pop_front() {
+            let distance = result.distances[&node];
+
+            if Some(node) == target || max_hops.is_some_and(|max_hops| distance >= max_hops) {
+                continue;
+            }
+
+            for &(adjacent, weight) in self.adjacents(node) {
+                // nodes are marked as visited when they are queued, so every node is queued once
+                if result.distances.contains_key(&adjacent) || !is_allowed(node, adjacent, weight) {
+                    continue;
+                }
+
+                result.distances.insert(adjacent, distance + 1);
+                result.parents.insert(adjacent, node);
+                result.order.push(adjacent);
+
+                if Some(adjacent) == target {
+                    return result;
+                }
+
+                queue.push_back(adjacent);
+            }
+        }
+
+        return result;
+    }
+}
@@ -0,0 +1,98 @@
+use crate::{DijkstraResult, Graph, Weight};
+use std::collections::HashSet;
+use std::hash::Hash;
+
+type NodePredicate<'a, N> = Box<dyn Fn(N) -> bool + 'a>;
+type EdgePredicate<'a, N, E> = Box<dyn Fn(N, N, E) -> bool + 'a>;
+
+/// Read-only view of a graph which hides nodes and edges not matching its predicates, created by `Graph::view()`.
+/// Searches run on the view see only the remaining part of the graph, without copying or changing it.
+pub struct GraphView<'a, N = u32, E = i32> {
+    graph: &'a Graph<N, E>,
+    node_predicates: Vec<NodePredicate<'a, N>>,
+    edge_predicates: Vec<EdgePredicate<'a, N, E>>,
+}
+
+impl<N: Copy + Eq + Hash, E: Copy + PartialEq> Graph<N, E> {
+    /// Creates a view of the graph which shows every node and edge. Use `with_nodes()` and `with_edges()` to hide some of them.
+    pub fn view(&self) -> GraphView<'_, N, E> {
+        return GraphView { graph: self, node_predicates: Vec::new(), edge_predicates: Vec::new() };
+    }
+}
+
+impl<'a, N: Copy + Eq + Hash, E: Copy + PartialEq> GraphView<'a, N, E> {
+    /// Keeps only nodes for which `predicate(node)` returns `true`, together with their edges.
+    pub fn with_nodes(mut self, predicate: impl Fn(N) -> bool + 'a) -> Self {
+        self.node_predicates.push(Box::new(predicate));
+
+        return self;
+    }
+
+    /// Keeps only edges for which `predicate(source, target, weight)` returns `true`.
+    pub fn with_edges(mut self, predicate: impl Fn(N, N, E) -> bool + 'a) -> Self {
+        self.edge_predicates.push(Box::new(predicate));
+
+        return self;
+    }
+
+    /// Checks if a node is present in the graph and not hidden by the view.
+    pub fn has_node(&self, node: N) -> bool {
+        return self.graph.has_node(node) && self.node_predicates.iter().all(|predicate| predicate(node));
+    }
+
+    /// Checks if an edge is not hidden by the view, including its target.
+    fn is_allowed(&self, source: N, target: N, weight: E) -> bool {
+        return self.node_predicates.iter().all(|predicate| predicate(target))
+            && self.edge_predicates.iter().all(|predicate| predicate(source, target, weight));
+    }
+
+    /// Runs `Graph::depth_first_search()` on the view. Returns an empty set if `node` is hidden.
+    pub fn depth_first_search(&self, node: N) -> HashSet<N> {
+        if !self.has_node(node) {
+            return HashSet::new();
+        }
+
+        return self.graph.depth_first_search_filtered(node, |source, target, weight| self.is_allowed(source, target, weight));
+    }
+
+    /// Runs `Graph::breadth_first_search()` on the view. Returns `None` if `node` is hidden.
+    pub fn breadth_first_search(&self, node: N, target: N) -> Option<N> {
+        if !self.has_node(node) {
+            return None;
+        }
+
+        return self.graph.breadth_first_search_filtered(node, target, |source, target, weight| self.is_allowed(source, target, weight));
+    }
+}
+
+impl<N: Copy + Eq + Hash, E: Weight> GraphView<'_, N, E> {
+    /// Runs `Graph::shortest_path()` on the view. There is no path if `source` or `target` is hidden.
+    pub fn shortest_path(&self, source: N, target: N) -> DijkstraResult<N, E> {
+        if !self.has_node(source) {
+            return DijkstraResult { cost: None, parents: None, path: None };
+        }
+
+        let (distances, parents) = self.graph.dijkstra_filtered(source, Some(target), |source, target, weight| self.is_allowed(source, target, weight));
+
+        let Some(&cost) = distances.get(&target) else {
+            return DijkstraResult { cost: None, parents: None, path: None };
+        };
+
+        return self.graph.dijkstra_result(source, target, cost, parents);
+    }
+
+    /// Runs `Graph::astar()` on the view. There is no path if `source` or `target` is hidden.
+    pub fn astar(&self, source: N, target: N, heuristic: impl Fn(N, N) -> E) -> DijkstraResult<N, E> {
+        if !self.has_node(source) {
+            return DijkstraResult { cost: None, parents: None, path: None };
+        }
+
+        let (distances, parents) = self.graph.astar_search(source, target, heuristic, |source, target, weight| self.is_allowed(source, target, weight));
+
+        let Some(&cost) = distances.get(&target) else {
+            return DijkstraResult { cost: None, parents: None, path: None };
+        };
+
+        return self.graph.dijkstra_result(source, target, cost, parents);
+    }
+}